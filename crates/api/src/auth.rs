@@ -0,0 +1,115 @@
+use std::sync::Arc;
+
+use axum::extract::{Request, State};
+use axum::http::HeaderMap;
+use axum::middleware::Next;
+use axum::response::Response;
+
+use crate::config::ApiKeyScope;
+use crate::error::AppError;
+use crate::AppState;
+
+/// The scope a route requires, or `None` if it's public and needs no key
+/// at all.
+fn required_scope(path: &str) -> Option<ApiKeyScope> {
+    if path == "/health" {
+        return None;
+    }
+
+    if path.starts_with("/ingest")
+        || path.starts_with("/extract")
+        || path.starts_with("/index")
+        || path.starts_with("/communities")
+        || path.starts_with("/benchmark")
+        || path == "/cache/clear"
+    {
+        return Some(ApiKeyScope::Admin);
+    }
+
+    // /query/*, /stats, /metrics, /tasks, /cache/stats, /config, and
+    // anything else behind the router is readable with either scope.
+    Some(ApiKeyScope::ReadOnly)
+}
+
+/// `Admin` keys can do anything a `ReadOnly` key can; `ReadOnly` keys can't
+/// reach admin-only routes.
+fn scope_allows(granted: ApiKeyScope, required: ApiKeyScope) -> bool {
+    granted == ApiKeyScope::Admin || granted == required
+}
+
+fn extract_api_key(headers: &HeaderMap) -> Option<String> {
+    if let Some(value) = headers.get("x-api-key") {
+        return value.to_str().ok().map(str::to_string);
+    }
+
+    let auth = headers.get(axum::http::header::AUTHORIZATION)?;
+    let auth = auth.to_str().ok()?;
+    auth.strip_prefix("Bearer ").map(str::to_string)
+}
+
+/// Checks the request's API key against `AppConfig::auth` before it reaches
+/// routing. `/health` is exempt; every other route requires at least a
+/// `ReadOnly` key, and the mutating routes require `Admin`.
+pub async fn auth_middleware(
+    State(state): State<Arc<AppState>>,
+    req: Request,
+    next: Next,
+) -> Result<Response, AppError> {
+    let Some(required) = required_scope(req.uri().path()) else {
+        return Ok(next.run(req).await);
+    };
+
+    let key = extract_api_key(req.headers()).ok_or(AppError::Unauthorized)?;
+    let granted = *state
+        .config
+        .auth
+        .keys
+        .get(&key)
+        .ok_or(AppError::Unauthorized)?;
+
+    if !scope_allows(granted, required) {
+        return Err(AppError::Forbidden);
+    }
+
+    Ok(next.run(req).await)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn health_is_public() {
+        assert_eq!(required_scope("/health"), None);
+    }
+
+    #[test]
+    fn mutating_routes_require_admin() {
+        assert_eq!(required_scope("/ingest"), Some(ApiKeyScope::Admin));
+        assert_eq!(required_scope("/extract"), Some(ApiKeyScope::Admin));
+        assert_eq!(required_scope("/index"), Some(ApiKeyScope::Admin));
+        assert_eq!(required_scope("/communities"), Some(ApiKeyScope::Admin));
+        assert_eq!(required_scope("/benchmark"), Some(ApiKeyScope::Admin));
+        assert_eq!(required_scope("/cache/clear"), Some(ApiKeyScope::Admin));
+    }
+
+    #[test]
+    fn everything_else_only_needs_read_only() {
+        assert_eq!(required_scope("/query/local"), Some(ApiKeyScope::ReadOnly));
+        assert_eq!(required_scope("/stats"), Some(ApiKeyScope::ReadOnly));
+        assert_eq!(required_scope("/cache/stats"), Some(ApiKeyScope::ReadOnly));
+        assert_eq!(required_scope("/config"), Some(ApiKeyScope::ReadOnly));
+    }
+
+    #[test]
+    fn admin_keys_can_reach_read_only_routes() {
+        assert!(scope_allows(ApiKeyScope::Admin, ApiKeyScope::ReadOnly));
+        assert!(scope_allows(ApiKeyScope::Admin, ApiKeyScope::Admin));
+    }
+
+    #[test]
+    fn read_only_keys_cannot_reach_admin_routes() {
+        assert!(!scope_allows(ApiKeyScope::ReadOnly, ApiKeyScope::Admin));
+        assert!(scope_allows(ApiKeyScope::ReadOnly, ApiKeyScope::ReadOnly));
+    }
+}
@@ -1,62 +1,463 @@
 #![allow(dead_code)]
+use anyhow::Result;
 use dashmap::DashMap;
 use sha2::{Digest, Sha256};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
-pub struct Cache {
-    embeddings: Arc<DashMap<String, Vec<f32>>>,
-    llm_responses: Arc<DashMap<String, String>>,
+use crate::config::{CacheConfig, EvictionPolicy};
+
+/// Approximate in-memory size of a cached value, in bytes, so a store can
+/// bound itself by `max_weight_bytes` rather than entry count alone.
+trait Weighted {
+    fn weight_bytes(&self) -> usize;
+}
+
+impl Weighted for Vec<f32> {
+    fn weight_bytes(&self) -> usize {
+        self.len() * std::mem::size_of::<f32>()
+    }
+}
+
+impl Weighted for String {
+    fn weight_bytes(&self) -> usize {
+        self.len()
+    }
+}
+
+/// A value alongside the bookkeeping `BoundedStore` needs to expire and
+/// evict it: how heavy it is, when it was inserted (for TTL and FIFO), and
+/// how recently/often it's been touched (for LRU and LFU).
+struct Entry<V> {
+    value: V,
+    weight: usize,
+    inserted_at: Instant,
+    inserted_tick: u64,
+    last_accessed: u64,
+    frequency: u64,
+}
+
+/// Lock-free (via `DashMap`) cache store bounded by entry count *and*
+/// approximate byte weight, with lazy TTL expiry and a pluggable eviction
+/// policy. Every `get`/`set` stamps the entry with a monotonically
+/// increasing counter, used as the LRU/FIFO sort key; `get` also bumps a
+/// per-entry frequency counter for LFU.
+struct BoundedStore<V: Clone + Weighted> {
+    entries: DashMap<String, Entry<V>>,
     max_entries: usize,
+    max_weight_bytes: Option<usize>,
+    ttl: Option<Duration>,
+    policy: EvictionPolicy,
+    clock: AtomicU64,
+    total_weight: AtomicUsize,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    evictions: AtomicU64,
+}
+
+impl<V: Clone + Weighted> BoundedStore<V> {
+    fn new(cfg: &CacheConfig) -> Self {
+        Self {
+            entries: DashMap::new(),
+            max_entries: cfg.max_entries,
+            max_weight_bytes: cfg.max_weight_bytes,
+            ttl: cfg.ttl_secs.map(Duration::from_secs),
+            policy: cfg.eviction_policy,
+            clock: AtomicU64::new(0),
+            total_weight: AtomicUsize::new(0),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            evictions: AtomicU64::new(0),
+        }
+    }
+
+    fn tick(&self) -> u64 {
+        self.clock.fetch_add(1, Ordering::Relaxed)
+    }
+
+    fn is_expired(&self, entry: &Entry<V>) -> bool {
+        self.ttl
+            .map(|ttl| entry.inserted_at.elapsed() > ttl)
+            .unwrap_or(false)
+    }
+
+    fn get(&self, key: &str) -> Option<V> {
+        let tick = self.tick();
+
+        if let Some(entry) = self.entries.get(key) {
+            if self.is_expired(&entry) {
+                drop(entry);
+                self.remove(key);
+            }
+        }
+
+        match self.entries.get_mut(key) {
+            Some(mut entry) => {
+                entry.last_accessed = tick;
+                entry.frequency += 1;
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                Some(entry.value.clone())
+            }
+            None => {
+                self.misses.fetch_add(1, Ordering::Relaxed);
+                None
+            }
+        }
+    }
+
+    fn set(&self, key: String, value: V) {
+        let weight = value.weight_bytes();
+
+        if let Some(old) = self.entries.get(&key) {
+            self.total_weight.fetch_sub(old.weight, Ordering::Relaxed);
+        }
+
+        let tick = self.tick();
+        self.total_weight.fetch_add(weight, Ordering::Relaxed);
+        self.entries.insert(
+            key,
+            Entry {
+                value,
+                weight,
+                inserted_at: Instant::now(),
+                inserted_tick: tick,
+                last_accessed: tick,
+                frequency: 0,
+            },
+        );
+
+        self.evict_if_over_budget();
+    }
+
+    fn remove(&self, key: &str) {
+        if let Some((_, entry)) = self.entries.remove(key) {
+            self.total_weight.fetch_sub(entry.weight, Ordering::Relaxed);
+        }
+    }
+
+    fn over_budget(&self) -> bool {
+        let over_count = self.entries.len() > self.max_entries;
+        let over_weight = self
+            .max_weight_bytes
+            .map(|max| self.total_weight.load(Ordering::Relaxed) > max)
+            .unwrap_or(false);
+        over_count || over_weight
+    }
+
+    /// Drop every TTL-expired entry unconditionally, then - if still over
+    /// budget - evict the coldest 25% (at least one) by `policy`, and keep
+    /// trimming one at a time until back under the weight bound too.
+    fn evict_if_over_budget(&self) {
+        let expired: Vec<String> = self
+            .entries
+            .iter()
+            .filter(|r| self.is_expired(r.value()))
+            .map(|r| r.key().clone())
+            .collect();
+        for key in expired {
+            self.remove(&key);
+            self.evictions.fetch_add(1, Ordering::Relaxed);
+        }
+
+        if !self.over_budget() {
+            return;
+        }
+
+        let mut by_policy: Vec<(String, u64)> = self
+            .entries
+            .iter()
+            .map(|r| (r.key().clone(), self.policy_rank(r.value())))
+            .collect();
+        by_policy.sort_by_key(|(_, rank)| *rank);
+
+        let target = (self.max_entries / 4).max(1);
+        let mut evicted = 0;
+        for (key, _) in &by_policy {
+            if evicted >= target && !self.over_budget() {
+                break;
+            }
+            self.remove(key);
+            self.evictions.fetch_add(1, Ordering::Relaxed);
+            evicted += 1;
+        }
+    }
+
+    fn policy_rank(&self, entry: &Entry<V>) -> u64 {
+        match self.policy {
+            EvictionPolicy::Lru => entry.last_accessed,
+            EvictionPolicy::Lfu => entry.frequency,
+            EvictionPolicy::Fifo => entry.inserted_tick,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    fn clear(&self) {
+        self.entries.clear();
+        self.total_weight.store(0, Ordering::Relaxed);
+    }
+
+    fn hits(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    fn misses(&self) -> u64 {
+        self.misses.load(Ordering::Relaxed)
+    }
+
+    fn evictions(&self) -> u64 {
+        self.evictions.load(Ordering::Relaxed)
+    }
+}
+
+/// When a disk-backed store persists a freshly-written entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlushPolicy {
+    /// Persist every write immediately.
+    WriteThrough,
+    /// Buffer writes in memory; only persisted when `Cache::flush` is
+    /// called (e.g. from a periodic background task).
+    Batched,
+}
+
+/// On-disk L2 tier backing a `BoundedStore`: one file per key, named by its
+/// hex digest, under `dir`. Loaded lazily on a miss, written through (or
+/// batched, per `FlushPolicy`) on a write.
+struct DiskStore {
+    dir: PathBuf,
+    write_through: AtomicBool,
+    pending: DashMap<String, Vec<u8>>,
+}
+
+impl DiskStore {
+    fn new(dir: PathBuf, flush_policy: FlushPolicy) -> Self {
+        Self {
+            dir,
+            write_through: AtomicBool::new(flush_policy == FlushPolicy::WriteThrough),
+            pending: DashMap::new(),
+        }
+    }
+
+    fn set_flush_policy(&self, flush_policy: FlushPolicy) {
+        self.write_through
+            .store(flush_policy == FlushPolicy::WriteThrough, Ordering::Relaxed);
+    }
+
+    fn path_for(&self, key: &str) -> PathBuf {
+        self.dir.join(key)
+    }
+
+    fn read(&self, key: &str) -> Option<Vec<u8>> {
+        if let Some(buffered) = self.pending.get(key) {
+            return Some(buffered.clone());
+        }
+        std::fs::read(self.path_for(key)).ok()
+    }
+
+    fn write(&self, key: String, bytes: Vec<u8>) -> Result<()> {
+        if self.write_through.load(Ordering::Relaxed) {
+            self.write_to_disk(&key, &bytes)
+        } else {
+            self.pending.insert(key, bytes);
+            Ok(())
+        }
+    }
+
+    fn write_to_disk(&self, key: &str, bytes: &[u8]) -> Result<()> {
+        std::fs::create_dir_all(&self.dir)?;
+        std::fs::write(self.path_for(key), bytes)?;
+        Ok(())
+    }
+
+    /// Persist any batched writes to disk.
+    fn flush(&self) -> Result<()> {
+        for entry in self.pending.iter() {
+            self.write_to_disk(entry.key(), entry.value())?;
+        }
+        self.pending.clear();
+        Ok(())
+    }
+
+    /// Prune on-disk entries beyond `max_bytes`, oldest-modified first,
+    /// until the directory's total size is back under budget.
+    fn compact(&self, max_bytes: u64) -> Result<()> {
+        self.flush()?;
+
+        let entries = match std::fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut files = Vec::new();
+        let mut total = 0u64;
+        for entry in entries {
+            let entry = entry?;
+            let metadata = entry.metadata()?;
+            if !metadata.is_file() {
+                continue;
+            }
+            let modified = metadata.modified().unwrap_or(std::time::UNIX_EPOCH);
+            total += metadata.len();
+            files.push((entry.path(), metadata.len(), modified));
+        }
+
+        if total <= max_bytes {
+            return Ok(());
+        }
+
+        files.sort_by_key(|(_, _, modified)| *modified);
+        for (path, size, _) in files {
+            if total <= max_bytes {
+                break;
+            }
+            std::fs::remove_file(&path)?;
+            total = total.saturating_sub(size);
+        }
+
+        Ok(())
+    }
+
+    fn clear(&self) -> Result<()> {
+        self.pending.clear();
+        match std::fs::remove_dir_all(&self.dir) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+/// Serialize an embedding as raw little-endian `f32` bytes for compactness.
+fn encode_embedding(v: &[f32]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(v.len() * 4);
+    for x in v {
+        bytes.extend_from_slice(&x.to_le_bytes());
+    }
+    bytes
+}
+
+fn decode_embedding(bytes: &[u8]) -> Option<Vec<f32>> {
+    if bytes.len() % 4 != 0 {
+        return None;
+    }
+    Some(
+        bytes
+            .chunks_exact(4)
+            .map(|c| f32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+            .collect(),
+    )
+}
+
+struct Disk {
+    embeddings: DiskStore,
+    responses: DiskStore,
+}
+
+pub struct Cache {
+    embeddings: Arc<BoundedStore<Vec<f32>>>,
+    llm_responses: Arc<BoundedStore<String>>,
+    disk: Option<Arc<Disk>>,
+    /// Mirrors `CacheConfig::enabled`: gates `get_embedding`/
+    /// `get_llm_response` only. Writes always proceed regardless, so a
+    /// reads-disabled run (e.g. `OperationMode::Accurate`) still seeds the
+    /// cache for whoever reads it next.
+    reads_enabled: bool,
 }
 
 impl Cache {
-    pub fn new(max_entries: usize) -> Self {
+    pub fn new(cfg: &CacheConfig) -> Self {
+        Self {
+            embeddings: Arc::new(BoundedStore::new(cfg)),
+            llm_responses: Arc::new(BoundedStore::new(cfg)),
+            disk: None,
+            reads_enabled: cfg.enabled,
+        }
+    }
+
+    /// Like `new`, but backed by an on-disk L2 tier under `path` (one
+    /// sub-directory each for embeddings and LLM responses), so entries
+    /// evicted from memory - or lost to a process restart - can still be
+    /// loaded back in on the next lookup. Defaults to write-through; use
+    /// `with_flush_policy` for batched writes.
+    pub fn open(path: impl Into<PathBuf>, cfg: &CacheConfig) -> Self {
+        let path = path.into();
         Self {
-            embeddings: Arc::new(DashMap::new()),
-            llm_responses: Arc::new(DashMap::new()),
-            max_entries,
+            embeddings: Arc::new(BoundedStore::new(cfg)),
+            llm_responses: Arc::new(BoundedStore::new(cfg)),
+            disk: Some(Arc::new(Disk {
+                embeddings: DiskStore::new(path.join("embeddings"), FlushPolicy::WriteThrough),
+                responses: DiskStore::new(path.join("responses"), FlushPolicy::WriteThrough),
+            })),
+            reads_enabled: cfg.enabled,
         }
     }
 
+    /// Change how the on-disk tier flushes writes. No-op if this cache
+    /// wasn't opened with `open`.
+    pub fn with_flush_policy(self, flush_policy: FlushPolicy) -> Self {
+        if let Some(disk) = &self.disk {
+            disk.embeddings.set_flush_policy(flush_policy);
+            disk.responses.set_flush_policy(flush_policy);
+        }
+        self
+    }
+
     /// Cache an embedding
     pub fn set_embedding(&self, text: &str, embedding: Vec<f32>) {
-        if self.embeddings.len() >= self.max_entries {
-            // Simple eviction: clear 25% when full
-            let to_remove: Vec<_> = self.embeddings.iter()
-                .take(self.max_entries / 4)
-                .map(|r| r.key().clone())
-                .collect();
-            for key in to_remove {
-                self.embeddings.remove(&key);
+        let key = self.hash_text(text);
+        if let Some(disk) = &self.disk {
+            if let Err(e) = disk.embeddings.write(key.clone(), encode_embedding(&embedding)) {
+                tracing::warn!(error = %e, "Failed to persist embedding to disk cache");
             }
         }
-        let key = self.hash_text(text);
-        self.embeddings.insert(key, embedding);
+        self.embeddings.set(key, embedding);
     }
 
     pub fn get_embedding(&self, text: &str) -> Option<Vec<f32>> {
+        if !self.reads_enabled {
+            return None;
+        }
+
         let key = self.hash_text(text);
-        self.embeddings.get(&key).map(|r| r.value().clone())
+        if let Some(hit) = self.embeddings.get(&key) {
+            return Some(hit);
+        }
+
+        let vector = decode_embedding(&self.disk.as_ref()?.embeddings.read(&key)?)?;
+        self.embeddings.set(key, vector.clone());
+        Some(vector)
     }
 
     /// Cache an LLM response
     pub fn set_llm_response(&self, prompt: &str, response: String) {
-        if self.llm_responses.len() >= self.max_entries {
-            let to_remove: Vec<_> = self.llm_responses.iter()
-                .take(self.max_entries / 4)
-                .map(|r| r.key().clone())
-                .collect();
-            for key in to_remove {
-                self.llm_responses.remove(&key);
+        let key = self.hash_text(prompt);
+        if let Some(disk) = &self.disk {
+            if let Err(e) = disk.responses.write(key.clone(), response.clone().into_bytes()) {
+                tracing::warn!(error = %e, "Failed to persist LLM response to disk cache");
             }
         }
-        let key = self.hash_text(prompt);
-        self.llm_responses.insert(key, response);
+        self.llm_responses.set(key, response);
     }
 
     pub fn get_llm_response(&self, prompt: &str) -> Option<String> {
+        if !self.reads_enabled {
+            return None;
+        }
+
         let key = self.hash_text(prompt);
-        self.llm_responses.get(&key).map(|r| r.value().clone())
+        if let Some(hit) = self.llm_responses.get(&key) {
+            return Some(hit);
+        }
+
+        let bytes = self.disk.as_ref()?.responses.read(&key)?;
+        let response = String::from_utf8(bytes).ok()?;
+        self.llm_responses.set(key, response.clone());
+        Some(response)
     }
 
     fn hash_text(&self, text: &str) -> String {
@@ -69,12 +470,47 @@ impl Cache {
         CacheStats {
             embeddings_cached: self.embeddings.len(),
             llm_responses_cached: self.llm_responses.len(),
+            embedding_hits: self.embeddings.hits(),
+            embedding_misses: self.embeddings.misses(),
+            embedding_evictions: self.embeddings.evictions(),
+            llm_hits: self.llm_responses.hits(),
+            llm_misses: self.llm_responses.misses(),
+            llm_evictions: self.llm_responses.evictions(),
         }
     }
 
     pub fn clear(&self) {
         self.embeddings.clear();
         self.llm_responses.clear();
+        if let Some(disk) = &self.disk {
+            if let Err(e) = disk.embeddings.clear() {
+                tracing::warn!(error = %e, "Failed to clear on-disk embedding cache");
+            }
+            if let Err(e) = disk.responses.clear() {
+                tracing::warn!(error = %e, "Failed to clear on-disk response cache");
+            }
+        }
+    }
+
+    /// Persist any batched (non-write-through) writes to disk. No-op if
+    /// this cache wasn't opened with `open`.
+    pub fn flush(&self) -> Result<()> {
+        let Some(disk) = &self.disk else {
+            return Ok(());
+        };
+        disk.embeddings.flush()?;
+        disk.responses.flush()
+    }
+
+    /// Prune each on-disk store down to `max_bytes_per_store`, oldest-
+    /// modified entries first. No-op if this cache wasn't opened with
+    /// `open`.
+    pub fn compact(&self, max_bytes_per_store: u64) -> Result<()> {
+        let Some(disk) = &self.disk else {
+            return Ok(());
+        };
+        disk.embeddings.compact(max_bytes_per_store)?;
+        disk.responses.compact(max_bytes_per_store)
     }
 }
 
@@ -82,4 +518,24 @@ impl Cache {
 pub struct CacheStats {
     pub embeddings_cached: usize,
     pub llm_responses_cached: usize,
-}
\ No newline at end of file
+    pub embedding_hits: u64,
+    pub embedding_misses: u64,
+    pub embedding_evictions: u64,
+    pub llm_hits: u64,
+    pub llm_misses: u64,
+    pub llm_evictions: u64,
+}
+
+impl CacheStats {
+    /// Combined hit rate across both caches, in `[0.0, 1.0]`. `0.0` when
+    /// nothing has been looked up yet.
+    pub fn hit_rate(&self) -> f64 {
+        let hits = (self.embedding_hits + self.llm_hits) as f64;
+        let lookups = hits + (self.embedding_misses + self.llm_misses) as f64;
+        if lookups == 0.0 {
+            0.0
+        } else {
+            hits / lookups
+        }
+    }
+}
@@ -1,3 +1,6 @@
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -6,14 +9,62 @@ pub struct AppConfig {
     pub concurrency: ConcurrencyConfig,
     pub retry: RetryConfig,
     pub cache: CacheConfig,
+    pub auth: AuthConfig,
+    pub incremental: IncrementalConfig,
+    /// Connection details for the S3-compatible bucket `/ingest` reads from
+    /// when given an `s3://` path. `None` means no bucket is configured, so
+    /// an `s3://` path is rejected.
+    pub s3: Option<S3Config>,
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "lowercase")]
 pub enum OperationMode {
-    Fast,      // Use cached results aggressively, lower quality LLM
-    Accurate,  // Always fresh, best quality LLM
-    Balanced,  // Default: cache when available, good quality
+    Fast,        // Use cached results aggressively, lower quality LLM
+    Accurate,    // Always fresh, best quality LLM
+    Balanced,    // Default: cache when available, good quality
+    Incremental, // Resumable indexing of a large corpus via `IncrementalConfig`
+}
+
+/// Settings for `OperationMode::Incremental`: where the checkpoint tracking
+/// per-chunk extraction progress lives, and how chunks are identified
+/// across runs so an unedited chunk is recognized even after a reshuffle.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IncrementalConfig {
+    pub checkpoint_path: String,
+    /// Where `ingest::IngestManifest` persists the chunk-ID set from the
+    /// last ingest, so `/ingest` can diff against it instead of
+    /// re-embedding the whole corpus every run.
+    pub manifest_path: String,
+    /// Identify chunks by their content hash (survives a chunk moving to a
+    /// different offset or document) rather than `chunk_id`, which also
+    /// folds in `doc_id` and offset and so treats any reshuffle as new
+    /// work.
+    pub content_hashing: bool,
+}
+
+impl Default for IncrementalConfig {
+    fn default() -> Self {
+        Self {
+            checkpoint_path: "graphrag_checkpoint.json".to_string(),
+            manifest_path: "graphrag_manifest.json".to_string(),
+            content_hashing: true,
+        }
+    }
+}
+
+/// Connection details for an S3-compatible bucket (AWS S3, MinIO, R2, ...).
+/// The bucket and key prefix themselves come from the `s3://bucket/prefix`
+/// path passed to `/ingest`, not from config. Credentials are
+/// `skip_serializing` so they never come back out through `GET /config`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct S3Config {
+    pub endpoint: String,
+    pub region: String,
+    #[serde(skip_serializing)]
+    pub access_key: String,
+    #[serde(skip_serializing)]
+    pub secret_key: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -28,12 +79,72 @@ pub struct RetryConfig {
     pub max_retries: usize,
     pub initial_backoff_ms: u64,
     pub max_backoff_ms: u64,
+    pub jitter: JitterMode,
+}
+
+/// A jitter mode for retry delays around the exponential backoff cap. It is
+/// loaded, merged and reported with the rest of the config, but nothing
+/// reads it: the clients' retry helper, `common::retry::retry_with_backoff`,
+/// always sleeps a uniformly random duration in `[0, cap]`, whatever this
+/// field says.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum JitterMode {
+    /// No jitter: the full exponential cap.
+    None,
+    /// A uniformly random duration in `[0, cap]`.
+    Full,
+    /// `cap/2 + random(0, cap/2)`.
+    Equal,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CacheConfig {
+    /// Gates reads only: a disabled cache still accepts writes (see
+    /// `OperationMode::Accurate`, which writes fresh results for a later
+    /// `Balanced` run to read back without ever serving a stale one itself).
     pub enabled: bool,
     pub max_entries: usize,
+    /// Directory for the optional on-disk cache tier. `None` keeps the
+    /// cache purely in-memory, the pre-existing behavior.
+    pub persist_path: Option<String>,
+    /// How long a cached entry stays fresh. `None` means entries only age
+    /// out via `max_entries`/`max_weight_bytes`, never by elapsed time.
+    pub ttl_secs: Option<u64>,
+    /// Approximate total in-memory size bound across a store's entries, in
+    /// bytes, enforced alongside (not instead of) `max_entries`.
+    pub max_weight_bytes: Option<usize>,
+    pub eviction_policy: EvictionPolicy,
+}
+
+/// Which entries a cache store evicts first once it's over its
+/// entry-count or weight budget.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum EvictionPolicy {
+    /// Evict the least-recently-accessed entries first.
+    Lru,
+    /// Evict the least-frequently-accessed entries first.
+    Lfu,
+    /// Evict the oldest-inserted entries first, regardless of access.
+    Fifo,
+}
+
+/// What an API key is allowed to do. `Admin` is a superset of `ReadOnly`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiKeyScope {
+    ReadOnly,
+    Admin,
+}
+
+/// API keys and the scope each one grants, keyed by the raw key value sent
+/// in `Authorization: Bearer <key>` / `X-Api-Key`. Loaded from `AppConfig`
+/// (rather than hardcoded) so deployments can rotate or revoke keys by
+/// editing config, not recompiling.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AuthConfig {
+    pub keys: std::collections::HashMap<String, ApiKeyScope>,
 }
 
 impl Default for AppConfig {
@@ -49,11 +160,19 @@ impl Default for AppConfig {
                 max_retries: 3,
                 initial_backoff_ms: 1000,
                 max_backoff_ms: 10000,
+                jitter: JitterMode::Full,
             },
             cache: CacheConfig {
                 enabled: true,
                 max_entries: 10000,
+                persist_path: None,
+                ttl_secs: Some(3600),
+                max_weight_bytes: Some(256 * 1024 * 1024),
+                eviction_policy: EvictionPolicy::Lru,
             },
+            auth: AuthConfig::default(),
+            incremental: IncrementalConfig::default(),
+            s3: None,
         }
     }
 }
@@ -72,11 +191,19 @@ impl AppConfig {
                 max_retries: 2,
                 initial_backoff_ms: 500,
                 max_backoff_ms: 5000,
+                jitter: JitterMode::Full,
             },
             cache: CacheConfig {
                 enabled: true,
                 max_entries: 50000,
+                persist_path: None,
+                ttl_secs: Some(1800),
+                max_weight_bytes: Some(512 * 1024 * 1024),
+                eviction_policy: EvictionPolicy::Lru,
             },
+            auth: AuthConfig::default(),
+            incremental: IncrementalConfig::default(),
+            s3: None,
         }
     }
 
@@ -92,11 +219,451 @@ impl AppConfig {
                 max_retries: 5,
                 initial_backoff_ms: 2000,
                 max_backoff_ms: 20000,
+                jitter: JitterMode::Full,
             },
             cache: CacheConfig {
+                // Reads disabled (this run must never serve a stale result),
+                // but writes still land so a later `Balanced` run benefits
+                // from the fresh results this one computes.
                 enabled: false,
-                max_entries: 0,
+                max_entries: 10000,
+                persist_path: None,
+                ttl_secs: Some(7200),
+                max_weight_bytes: Some(256 * 1024 * 1024),
+                eviction_policy: EvictionPolicy::Lru,
             },
+            auth: AuthConfig::default(),
+            incremental: IncrementalConfig::default(),
+            s3: None,
+        }
+    }
+
+    /// Like `default()`, but with content-hash checkpointing turned on so
+    /// `ingest::checkpoint::resume` has something to resume from.
+    pub fn incremental_mode() -> Self {
+        Self {
+            mode: OperationMode::Incremental,
+            incremental: IncrementalConfig::default(),
+            ..Self::default()
+        }
+    }
+
+    fn from_mode(mode: OperationMode) -> Self {
+        match mode {
+            OperationMode::Fast => Self::fast_mode(),
+            OperationMode::Accurate => Self::accurate_mode(),
+            OperationMode::Balanced => Self::default(),
+            OperationMode::Incremental => Self::incremental_mode(),
+        }
+    }
+
+    /// Load config by merging three layers in priority order (each layer
+    /// overrides only the fields it sets, on top of the previous one):
+    ///
+    /// 1. An `OperationMode` preset as the base, chosen by `GRAPHRAG_MODE`
+    ///    (`fast`/`accurate`/`balanced`/`incremental`), defaulting to
+    ///    `Balanced`.
+    /// 2. A TOML or YAML file at `GRAPHRAG_CONFIG`, or [`DEFAULT_CONFIG_PATH`]
+    ///    if that's unset and the file exists. A missing default file is not
+    ///    an error; a `GRAPHRAG_CONFIG` path that can't be read, or a file
+    ///    that can't be parsed, is.
+    /// 3. Environment variables prefixed `GRAPHRAG_`, with `__` as the
+    ///    nesting separator, e.g.
+    ///    `GRAPHRAG_CONCURRENCY__MAX_CONCURRENT_LLM_CALLS=8` overrides
+    ///    `concurrency.max_concurrent_llm_calls`.
+    ///
+    /// The merged result is passed through [`AppConfig::validate`] before
+    /// being returned.
+    pub fn load() -> Result<Self> {
+        let mode = match std::env::var("GRAPHRAG_MODE") {
+            Ok(raw) => parse_mode(&raw)?,
+            Err(_) => OperationMode::Balanced,
+        };
+        let mut config = Self::from_mode(mode);
+
+        if let Some(path) = config_file_path() {
+            let patch = load_config_file(&path)?;
+            config.apply_patch(patch);
+        }
+
+        config.apply_env_overrides()?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    fn apply_patch(&mut self, patch: ConfigPatch) {
+        if let Some(mode) = patch.mode {
+            self.mode = mode;
+        }
+        if let Some(c) = patch.concurrency {
+            if let Some(v) = c.max_concurrent_llm_calls {
+                self.concurrency.max_concurrent_llm_calls = v;
+            }
+            if let Some(v) = c.max_concurrent_extractions {
+                self.concurrency.max_concurrent_extractions = v;
+            }
+            if let Some(v) = c.request_timeout_secs {
+                self.concurrency.request_timeout_secs = v;
+            }
+        }
+        if let Some(r) = patch.retry {
+            if let Some(v) = r.max_retries {
+                self.retry.max_retries = v;
+            }
+            if let Some(v) = r.initial_backoff_ms {
+                self.retry.initial_backoff_ms = v;
+            }
+            if let Some(v) = r.max_backoff_ms {
+                self.retry.max_backoff_ms = v;
+            }
+            if let Some(v) = r.jitter {
+                self.retry.jitter = v;
+            }
+        }
+        if let Some(c) = patch.cache {
+            if let Some(v) = c.enabled {
+                self.cache.enabled = v;
+            }
+            if let Some(v) = c.max_entries {
+                self.cache.max_entries = v;
+            }
+            if let Some(v) = c.persist_path {
+                self.cache.persist_path = Some(v);
+            }
+            if let Some(v) = c.ttl_secs {
+                self.cache.ttl_secs = Some(v);
+            }
+            if let Some(v) = c.max_weight_bytes {
+                self.cache.max_weight_bytes = Some(v);
+            }
+            if let Some(v) = c.eviction_policy {
+                self.cache.eviction_policy = v;
+            }
+        }
+        if let Some(auth) = patch.auth {
+            self.auth = auth;
+        }
+        if let Some(i) = patch.incremental {
+            if let Some(v) = i.checkpoint_path {
+                self.incremental.checkpoint_path = v;
+            }
+            if let Some(v) = i.manifest_path {
+                self.incremental.manifest_path = v;
+            }
+            if let Some(v) = i.content_hashing {
+                self.incremental.content_hashing = v;
+            }
+        }
+        if let Some(s3) = patch.s3 {
+            self.s3 = Some(s3);
+        }
+    }
+
+    /// Apply `GRAPHRAG_<SECTION>__<FIELD>` overrides on top of the preset +
+    /// file layers. Unknown or absent env vars are left alone; a present
+    /// one that fails to parse is reported by field name.
+    fn apply_env_overrides(&mut self) -> Result<()> {
+        if let Some(v) = env_field("CONCURRENCY", "MAX_CONCURRENT_LLM_CALLS")? {
+            self.concurrency.max_concurrent_llm_calls = v;
+        }
+        if let Some(v) = env_field("CONCURRENCY", "MAX_CONCURRENT_EXTRACTIONS")? {
+            self.concurrency.max_concurrent_extractions = v;
+        }
+        if let Some(v) = env_field("CONCURRENCY", "REQUEST_TIMEOUT_SECS")? {
+            self.concurrency.request_timeout_secs = v;
+        }
+        if let Some(v) = env_field("RETRY", "MAX_RETRIES")? {
+            self.retry.max_retries = v;
+        }
+        if let Some(v) = env_field("RETRY", "INITIAL_BACKOFF_MS")? {
+            self.retry.initial_backoff_ms = v;
+        }
+        if let Some(v) = env_field("RETRY", "MAX_BACKOFF_MS")? {
+            self.retry.max_backoff_ms = v;
+        }
+        if let Some(v) = env_field("CACHE", "ENABLED")? {
+            self.cache.enabled = v;
+        }
+        if let Some(v) = env_field("CACHE", "MAX_ENTRIES")? {
+            self.cache.max_entries = v;
+        }
+        if let Some(v) = env_field("CACHE", "TTL_SECS")? {
+            self.cache.ttl_secs = Some(v);
+        }
+        if let Some(v) = env_field("CACHE", "MAX_WEIGHT_BYTES")? {
+            self.cache.max_weight_bytes = Some(v);
+        }
+        if let Some(v) = env_field("INCREMENTAL", "CHECKPOINT_PATH")? {
+            self.incremental.checkpoint_path = v;
+        }
+        if let Some(v) = env_field("INCREMENTAL", "MANIFEST_PATH")? {
+            self.incremental.manifest_path = v;
+        }
+        if let Some(v) = env_field("INCREMENTAL", "CONTENT_HASHING")? {
+            self.incremental.content_hashing = v;
+        }
+        if let Some(v) = env_field::<String>("S3", "ENDPOINT")? {
+            self.s3.get_or_insert_with(S3Config::default).endpoint = v;
+        }
+        if let Some(v) = env_field::<String>("S3", "REGION")? {
+            self.s3.get_or_insert_with(S3Config::default).region = v;
+        }
+        if let Some(v) = env_field::<String>("S3", "ACCESS_KEY")? {
+            self.s3.get_or_insert_with(S3Config::default).access_key = v;
+        }
+        if let Some(v) = env_field::<String>("S3", "SECRET_KEY")? {
+            self.s3.get_or_insert_with(S3Config::default).secret_key = v;
         }
+        Ok(())
+    }
+
+    /// Reject config combinations that would silently misbehave rather
+    /// than let them through to whatever uses them first.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.retry.initial_backoff_ms > self.retry.max_backoff_ms {
+            return Err(ConfigError::new(
+                "retry.initial_backoff_ms",
+                "must not be greater than retry.max_backoff_ms",
+            ));
+        }
+        if self.concurrency.max_concurrent_llm_calls == 0 {
+            return Err(ConfigError::new(
+                "concurrency.max_concurrent_llm_calls",
+                "must be at least 1",
+            ));
+        }
+        if self.cache.enabled && self.cache.max_entries == 0 {
+            return Err(ConfigError::new(
+                "cache.max_entries",
+                "must be at least 1 when cache.enabled is true",
+            ));
+        }
+        if self.concurrency.request_timeout_secs == 0 {
+            return Err(ConfigError::new(
+                "concurrency.request_timeout_secs",
+                "must be at least 1",
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Default location `AppConfig::load` looks for a config file when
+/// `GRAPHRAG_CONFIG` isn't set.
+const DEFAULT_CONFIG_PATH: &str = "graphrag.toml";
+
+fn config_file_path() -> Option<PathBuf> {
+    match std::env::var("GRAPHRAG_CONFIG") {
+        Ok(path) => Some(PathBuf::from(path)),
+        Err(_) => {
+            let default = PathBuf::from(DEFAULT_CONFIG_PATH);
+            default.exists().then_some(default)
+        }
+    }
+}
+
+fn load_config_file(path: &PathBuf) -> Result<ConfigPatch> {
+    let raw = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read config file {}", path.display()))?;
+
+    let is_yaml = matches!(
+        path.extension().and_then(|e| e.to_str()),
+        Some("yaml") | Some("yml")
+    );
+
+    if is_yaml {
+        serde_yaml::from_str(&raw)
+            .with_context(|| format!("failed to parse config file {} as YAML", path.display()))
+    } else {
+        toml::from_str(&raw)
+            .with_context(|| format!("failed to parse config file {} as TOML", path.display()))
+    }
+}
+
+fn parse_mode(raw: &str) -> Result<OperationMode> {
+    match raw.to_lowercase().as_str() {
+        "fast" => Ok(OperationMode::Fast),
+        "accurate" => Ok(OperationMode::Accurate),
+        "balanced" => Ok(OperationMode::Balanced),
+        "incremental" => Ok(OperationMode::Incremental),
+        other => anyhow::bail!(
+            "GRAPHRAG_MODE must be one of fast/accurate/balanced/incremental, got '{other}'"
+        ),
+    }
+}
+
+/// Read `GRAPHRAG_<section>__<field>` and parse it as `T`, if set.
+fn env_field<T: std::str::FromStr>(section: &str, field: &str) -> Result<Option<T>>
+where
+    T::Err: std::fmt::Display,
+{
+    let key = format!("GRAPHRAG_{section}__{field}");
+    match std::env::var(&key) {
+        Ok(raw) => raw
+            .parse()
+            .map(Some)
+            .map_err(|e| anyhow::anyhow!("invalid value for {key}: {e}")),
+        Err(_) => Ok(None),
+    }
+}
+
+/// Every field of [`AppConfig`] as an optional override, deserialized from
+/// a config file and merged on top of the `OperationMode` preset. Absent
+/// fields (including whole absent sections) leave the preset's value
+/// untouched.
+#[derive(Debug, Default, Deserialize)]
+struct ConfigPatch {
+    mode: Option<OperationMode>,
+    concurrency: Option<ConcurrencyPatch>,
+    retry: Option<RetryPatch>,
+    cache: Option<CachePatch>,
+    auth: Option<AuthConfig>,
+    incremental: Option<IncrementalPatch>,
+    s3: Option<S3Config>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct ConcurrencyPatch {
+    max_concurrent_llm_calls: Option<usize>,
+    max_concurrent_extractions: Option<usize>,
+    request_timeout_secs: Option<u64>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct RetryPatch {
+    max_retries: Option<usize>,
+    initial_backoff_ms: Option<u64>,
+    max_backoff_ms: Option<u64>,
+    jitter: Option<JitterMode>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct CachePatch {
+    enabled: Option<bool>,
+    max_entries: Option<usize>,
+    persist_path: Option<String>,
+    ttl_secs: Option<u64>,
+    max_weight_bytes: Option<usize>,
+    eviction_policy: Option<EvictionPolicy>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct IncrementalPatch {
+    checkpoint_path: Option<String>,
+    manifest_path: Option<String>,
+    content_hashing: Option<bool>,
+}
+
+/// A config field that failed [`AppConfig::validate`], naming the
+/// offending field so the caller doesn't have to guess which of three
+/// merged layers set it.
+#[derive(Debug, Clone)]
+pub struct ConfigError {
+    pub field: &'static str,
+    pub message: &'static str,
+}
+
+impl ConfigError {
+    fn new(field: &'static str, message: &'static str) -> Self {
+        Self { field, message }
+    }
+}
+
+impl std::fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid config: {} {}", self.field, self.message)
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn apply_patch_overrides_only_the_fields_it_sets() {
+        let mut config = AppConfig::fast_mode();
+        let patch = ConfigPatch {
+            retry: Some(RetryPatch {
+                max_retries: Some(9),
+                initial_backoff_ms: None,
+                max_backoff_ms: None,
+                jitter: None,
+            }),
+            ..ConfigPatch::default()
+        };
+
+        config.apply_patch(patch);
+
+        assert_eq!(config.retry.max_retries, 9);
+        // Untouched retry fields keep fast_mode()'s preset values.
+        assert_eq!(config.retry.initial_backoff_ms, 500);
+        assert_eq!(config.retry.max_backoff_ms, 5000);
+        // Untouched sections are left alone entirely.
+        assert_eq!(config.mode, OperationMode::Fast);
+        assert_eq!(config.cache.max_entries, 50000);
+    }
+
+    #[test]
+    fn apply_patch_full_replaces_auth_and_s3() {
+        let mut config = AppConfig::default();
+        let mut keys = std::collections::HashMap::new();
+        keys.insert("secret".to_string(), ApiKeyScope::Admin);
+        let patch = ConfigPatch {
+            auth: Some(AuthConfig { keys: keys.clone() }),
+            s3: Some(S3Config {
+                endpoint: "https://s3.example.com".to_string(),
+                region: "us-east-1".to_string(),
+                access_key: "ak".to_string(),
+                secret_key: "sk".to_string(),
+            }),
+            ..ConfigPatch::default()
+        };
+
+        config.apply_patch(patch);
+
+        assert_eq!(config.auth.keys, keys);
+        assert_eq!(config.s3.unwrap().region, "us-east-1");
+    }
+
+    // `std::env::set_var`/`remove_var` mutate process-wide state, which
+    // races against other env-var-touching tests under the default
+    // multi-threaded test runner - so every case that needs one lives in
+    // this single test rather than across several, each with its own var
+    // name to avoid stepping on each other within the test itself.
+    #[test]
+    fn env_field_reads_set_values_and_leaves_unset_ones_alone() {
+        let present_key = "GRAPHRAG_TEST__ENV_FIELD_PRESENT";
+        let bad_key = "GRAPHRAG_TEST__ENV_FIELD_BAD";
+
+        std::env::set_var(present_key, "42");
+        std::env::set_var(bad_key, "not-a-number");
+
+        let present: Option<u64> = env_field("TEST", "ENV_FIELD_PRESENT").unwrap();
+        let absent: Option<u64> = env_field("TEST", "ENV_FIELD_ABSENT_XYZ").unwrap();
+        let bad: Result<Option<u64>> = env_field("TEST", "ENV_FIELD_BAD");
+
+        std::env::remove_var(present_key);
+        std::env::remove_var(bad_key);
+
+        assert_eq!(present, Some(42));
+        assert_eq!(absent, None);
+        assert!(bad.is_err());
+    }
+
+    #[test]
+    fn validate_rejects_backoff_bounds_that_cant_be_satisfied() {
+        let mut config = AppConfig::default();
+        config.retry.initial_backoff_ms = 5000;
+        config.retry.max_backoff_ms = 1000;
+
+        let err = config.validate().unwrap_err();
+        assert_eq!(err.field, "retry.initial_backoff_ms");
+    }
+
+    #[test]
+    fn validate_accepts_the_default_config() {
+        assert!(AppConfig::default().validate().is_ok());
     }
 }
\ No newline at end of file
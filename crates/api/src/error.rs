@@ -0,0 +1,134 @@
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use serde::Serialize;
+
+/// Structured API error with a stable, machine-readable `code` and the
+/// correct HTTP status, so handlers can report what actually went wrong
+/// instead of collapsing every failure into a blanket 500.
+#[derive(Debug)]
+pub enum AppError {
+    /// A specific chunk file the caller asked for doesn't exist.
+    ChunkNotFound(String),
+    /// No task with the given uid has been enqueued.
+    TaskNotFound(u64),
+    /// The path given to `/ingest` doesn't exist on disk.
+    PathNotFound(String),
+    /// The path given to `/ingest` exists but is neither a file nor a
+    /// directory, or another request field is malformed.
+    InvalidPath(String),
+    /// An uploaded file's extension isn't one `ingest::ingest_file` knows
+    /// how to read.
+    UnsupportedFileType(String),
+    /// A chunk or extracted-chunk file on disk failed to parse as JSON.
+    BadChunkJson(String),
+    /// Neo4j could not be reached or returned an error.
+    Neo4jUnavailable(String),
+    /// Qdrant could not be reached or returned an error.
+    QdrantUnavailable(String),
+    /// The configured LLM failed to produce a response.
+    LlmFailure(String),
+    /// Entity/relation extraction failed for a chunk.
+    ExtractionFailed(String),
+    /// Anything else (local I/O, serialization, ...) that doesn't fit a
+    /// more specific bucket above.
+    Internal(String),
+    /// No API key (or an unrecognized one) was presented for a route that
+    /// requires one.
+    Unauthorized,
+    /// A valid API key was presented, but its scope doesn't permit this
+    /// route.
+    Forbidden,
+}
+
+#[derive(Serialize)]
+struct ErrorBody {
+    code: &'static str,
+    message: String,
+    #[serde(rename = "type")]
+    kind: &'static str,
+}
+
+impl AppError {
+    fn code(&self) -> &'static str {
+        match self {
+            AppError::ChunkNotFound(_) => "chunk_not_found",
+            AppError::TaskNotFound(_) => "task_not_found",
+            AppError::PathNotFound(_) => "path_not_found",
+            AppError::InvalidPath(_) => "invalid_path",
+            AppError::UnsupportedFileType(_) => "unsupported_file_type",
+            AppError::BadChunkJson(_) => "bad_chunk_json",
+            AppError::Neo4jUnavailable(_) => "neo4j_unavailable",
+            AppError::QdrantUnavailable(_) => "qdrant_unavailable",
+            AppError::LlmFailure(_) => "llm_failure",
+            AppError::ExtractionFailed(_) => "extraction_failed",
+            AppError::Internal(_) => "internal_error",
+            AppError::Unauthorized => "unauthorized",
+            AppError::Forbidden => "forbidden",
+        }
+    }
+
+    fn status(&self) -> StatusCode {
+        match self {
+            AppError::ChunkNotFound(_) | AppError::PathNotFound(_) | AppError::TaskNotFound(_) => {
+                StatusCode::NOT_FOUND
+            }
+            AppError::InvalidPath(_) | AppError::BadChunkJson(_) | AppError::UnsupportedFileType(_) => {
+                StatusCode::BAD_REQUEST
+            }
+            AppError::Neo4jUnavailable(_) | AppError::QdrantUnavailable(_) => {
+                StatusCode::SERVICE_UNAVAILABLE
+            }
+            AppError::LlmFailure(_) => StatusCode::BAD_GATEWAY,
+            AppError::ExtractionFailed(_) | AppError::Internal(_) => {
+                StatusCode::INTERNAL_SERVER_ERROR
+            }
+            AppError::Unauthorized => StatusCode::UNAUTHORIZED,
+            AppError::Forbidden => StatusCode::FORBIDDEN,
+        }
+    }
+
+    fn message(&self) -> String {
+        match self {
+            AppError::ChunkNotFound(detail) => format!("Chunk not found: {}", detail),
+            AppError::TaskNotFound(uid) => format!("No task with uid {}", uid),
+            AppError::PathNotFound(detail) => format!("Path not found: {}", detail),
+            AppError::InvalidPath(detail) => format!("Invalid path: {}", detail),
+            AppError::UnsupportedFileType(ext) => format!("Unsupported file extension: '{}'", ext),
+            AppError::BadChunkJson(detail) => format!("Malformed chunk JSON: {}", detail),
+            AppError::Neo4jUnavailable(detail) => format!("Neo4j is unavailable: {}", detail),
+            AppError::QdrantUnavailable(detail) => format!("Qdrant is unavailable: {}", detail),
+            AppError::LlmFailure(detail) => format!("LLM request failed: {}", detail),
+            AppError::ExtractionFailed(detail) => format!("Extraction failed: {}", detail),
+            AppError::Internal(detail) => detail.clone(),
+            AppError::Unauthorized => {
+                "Missing or invalid API key. Send it as 'Authorization: Bearer <key>' or 'X-Api-Key: <key>'".to_string()
+            }
+            AppError::Forbidden => "This API key's scope does not permit this route".to_string(),
+        }
+    }
+
+    /// The human-readable message, for callers (like the task worker) that
+    /// need it outside of an HTTP response.
+    pub(crate) fn detail(&self) -> String {
+        self.message()
+    }
+}
+
+impl IntoResponse for AppError {
+    fn into_response(self) -> Response {
+        let status = self.status();
+        let kind = if status.is_client_error() {
+            "invalid_request"
+        } else {
+            "internal"
+        };
+        let body = ErrorBody {
+            code: self.code(),
+            message: self.message(),
+            kind,
+        };
+
+        (status, Json(body)).into_response()
+    }
+}
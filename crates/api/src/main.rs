@@ -1,5 +1,5 @@
 use axum::{
-    extract::{State},
+    extract::{Multipart, Path, Query, State},
     http::StatusCode,
     routing::{get, post},
     Json, Router,
@@ -7,7 +7,7 @@ use axum::{
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use tokio::sync::{mpsc, Mutex};
 use tracing_subscriber;
 use qdrant_client::Qdrant;
 
@@ -17,15 +17,27 @@ use qdrant_client::Qdrant;
 
 mod config;
 mod cache;
-mod retry;
+mod error;
 mod metrics;
 mod request_id;
+mod tasks;
+mod auth;
+mod search;
+mod scheduler;
 
 use config::AppConfig;
 use cache::Cache;
-use retry::RetryPolicy;
+use error::AppError;
 use metrics::{Metrics, TimedOperation};
 use request_id::{RequestId, request_id_middleware};
+use tasks::{Task, TaskController, TaskInfo, TaskKind, TaskStatus, TaskStore};
+use auth::auth_middleware;
+use search::{SearchParams, SearchResponse};
+use scheduler::{Scheduler, SchedulerError};
+
+/// Background task workers are fed through a channel of this depth before
+/// `TaskController::enqueue` starts waiting for room.
+const TASK_QUEUE_DEPTH: usize = 256;
 
 #[derive(Clone)]
 struct AppState {
@@ -38,8 +50,9 @@ struct AppState {
     config: AppConfig,
     cache: Arc<Cache>,
     metrics: Arc<Metrics>,
-    retry_policy: Arc<RetryPolicy>,
-    llm_semaphore: Arc<tokio::sync::Semaphore>,
+    scheduler: Arc<Scheduler>,
+    task_store: TaskStore,
+    task_controller: TaskController,
 }
 
 #[derive(Serialize)]
@@ -48,7 +61,7 @@ struct HealthResponse {
     neo4j: String,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, Serialize, Clone)]
 struct IngestRequest {
     path: String,
 }
@@ -59,7 +72,7 @@ struct IngestResponse {
     doc_ids: Vec<String>,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, Serialize, Clone)]
 struct ExtractRequest {
     /// Optional: extract from specific chunk file
     chunk_file: Option<String>,
@@ -72,6 +85,24 @@ struct ExtractResponse {
     relations_extracted: usize,
 }
 
+/// Returned immediately by every pipeline-stage endpoint; the actual work
+/// runs on the background task worker and the client polls `GET
+/// /tasks/{uid}` for the result.
+#[derive(Serialize)]
+struct EnqueuedResponse {
+    task_uid: u64,
+    status: &'static str,
+}
+
+#[derive(Deserialize, Serialize, Clone)]
+struct BenchmarkRequest {
+    /// Which built-in test set to run. Only `"default"` exists today; the
+    /// field is here so more can be added later without an API break.
+    test_set: Option<String>,
+    /// `top_k` passed to each method under test. Defaults to 5.
+    top_k: Option<usize>,
+}
+
 // Added Search Request/Response structs
 // #[derive(Deserialize)]
 // struct SearchRequest {
@@ -94,28 +125,32 @@ async fn main() {
         .json()
         .init();
 
-    // Load config
-    let config = AppConfig::default(); // Or load from file/env
-    
+    // Load config: `OperationMode` preset, then config file, then env
+    // overrides, validated before anything else starts up.
+    let config = match AppConfig::load() {
+        Ok(config) => config,
+        Err(e) => {
+            tracing::error!(error = %e, "Invalid configuration");
+            std::process::exit(1);
+        }
+    };
+
     tracing::info!(mode = ?config.mode, "Starting GraphRAG API");
 
-    // Initialize cache
-    let cache = Arc::new(Cache::new(config.cache.max_entries));
+    // Initialize cache, backed by an on-disk tier if one is configured so
+    // cached embeddings/LLM responses survive a restart.
+    let cache = Arc::new(match &config.cache.persist_path {
+        Some(path) => Cache::open(path, &config.cache),
+        None => Cache::new(&config.cache),
+    });
 
     // Initialize metrics
     let metrics = Metrics::new();
 
-    // Initialize retry policy
-    let retry_policy = Arc::new(RetryPolicy::new(
-        config.retry.max_retries,
-        config.retry.initial_backoff_ms,
-        config.retry.max_backoff_ms,
-    ));
-
-    // Concurrency control
-    let llm_semaphore = Arc::new(tokio::sync::Semaphore::new(
-        config.concurrency.max_concurrent_llm_calls
-    ));
+    // Concurrency control: adaptive permit pools for LLM calls and
+    // extractions, enforcing `config.concurrency` for real instead of
+    // leaving it as a number nothing reads.
+    let scheduler = Arc::new(Scheduler::new(&config.concurrency));
 
     // Connect to Neo4j
     let neo4j_graph = neo4rs::Graph::new(
@@ -129,8 +164,22 @@ async fn main() {
     // Create extractor
     let extractor = extract::Extractor::default();
 
+    // Thread the layered `config.retry` settings into the embedding/LLM
+    // clients that actually make HTTP calls, rather than leaving them on
+    // each client's hardcoded default.
+    let index_retry_cfg = common::retry::RetryConfig {
+        max_retries: config.retry.max_retries,
+        initial_backoff_ms: config.retry.initial_backoff_ms,
+        max_backoff_ms: config.retry.max_backoff_ms,
+    };
+    let query_retry_cfg = common::retry::RetryConfig {
+        max_retries: config.retry.max_retries,
+        initial_backoff_ms: config.retry.initial_backoff_ms,
+        max_backoff_ms: config.retry.max_backoff_ms,
+    };
+
     // Create embedding client
-    let embedding_client = index::EmbeddingClient::default();
+    let embedding_client = index::EmbeddingClient::default().with_retry_config(index_retry_cfg.clone());
 
     // Create Qdrant indexer (using REST API)
     let _qdrant_client = Qdrant::from_url("http://localhost:6333")
@@ -139,7 +188,7 @@ async fn main() {
 
     let qdrant_indexer = index::QdrantIndexer::new(
         "http://localhost:6333".to_string(),
-        embedding_client,
+        Box::new(embedding_client),
         "graphrag_chunks".to_string(),
     );
     
@@ -157,8 +206,8 @@ async fn main() {
         community_summarizer,
     );
 
-    let query_llm = query::QueryLLM::default();
-    let query_embedding_client = index::EmbeddingClient::default();
+    let query_llm = query::QueryLLM::default().with_retry_config(query_retry_cfg);
+    let query_embedding_client = index::EmbeddingClient::default().with_retry_config(index_retry_cfg);
 
     let local_search = query::LocalSearchEngine::new(
         //qdrant_client.clone(),
@@ -170,10 +219,14 @@ async fn main() {
     );
 
     let global_search = query::GlobalSearchEngine::new(
-        query_embedding_client,
+        Box::new(query_embedding_client),
         query_llm,
     );
 
+    let task_store = TaskStore::new();
+    let (task_sender, task_receiver) = mpsc::channel::<Task>(TASK_QUEUE_DEPTH);
+    let task_controller = TaskController::new(task_sender, task_store.clone());
+
     let state = Arc::new(AppState {
         neo4j_graph,
         extractor: Arc::new(Mutex::new(extractor)),
@@ -184,26 +237,44 @@ async fn main() {
         config,
         cache,
         metrics,
-        retry_policy,
-        llm_semaphore,
+        scheduler,
+        task_store,
+        task_controller,
     });
 
+    // Background worker: owns the Extractor/Indexer/CommunityDetector
+    // (via `state`) and drains `task_receiver` so /ingest, /extract,
+    // /index, and /communities can return immediately instead of blocking
+    // the request for the whole pipeline run.
+    tokio::spawn(tasks::run_task_worker(task_receiver, state.clone()));
+
     // Build router
     let app = Router::new()
         .route("/health", post(health_check))
         .route("/health", get(health_check))
         .route("/ingest", post(ingest_document))
+        .route("/ingest/upload", post(ingest_upload))
         .route("/extract", post(extract_chunks))
         .route("/index", post(index_data))
         //.route("/stats", get(get_stats))
         .route("/communities", post(detect_communities))
+        .route("/benchmark", post(run_benchmark_endpoint))
         .route("/query/local", post(query_local))
         .route("/query/global", post(query_global))
+        .route("/tasks", get(list_tasks))
+        .route("/tasks/:uid", get(get_task))
+        .route("/search", get(search_chunks))
         .route("/stats", get(get_stats))
         .route("/metrics", get(get_metrics))
+        .route("/metrics/prometheus", get(get_metrics_prometheus))
         .route("/cache/stats", get(get_cache_stats))
         .route("/cache/clear", post(clear_cache))
+        .route("/scheduler/stats", get(get_scheduler_stats))
         .route("/config", get(get_config))
+        .layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            auth_middleware,
+        ))
         .layer(axum::middleware::from_fn(request_id_middleware))
         .with_state(state);
 
@@ -223,12 +294,43 @@ async fn get_metrics(
     Json(state.metrics.snapshot())
 }
 
+/// Metrics in OpenMetrics text exposition format, for a Prometheus scrape
+/// target. `/metrics` stays JSON for existing consumers of the summary
+/// snapshot.
+async fn get_metrics_prometheus(
+    State(state): State<Arc<AppState>>,
+) -> impl axum::response::IntoResponse {
+    (
+        [(
+            axum::http::header::CONTENT_TYPE,
+            "application/openmetrics-text; version=1.0.0; charset=utf-8",
+        )],
+        state.metrics.render_prometheus(),
+    )
+}
+
 async fn get_cache_stats(
     State(state): State<Arc<AppState>>,
 ) -> Json<cache::CacheStats> {
     Json(state.cache.stats())
 }
 
+/// Current effective permit counts for the scheduler's LLM/extraction
+/// pools, so operators can see an `OperationMode` preset's concurrency
+/// degrade under an adaptive downshift instead of assuming it's fixed.
+#[derive(Serialize)]
+struct SchedulerStats {
+    llm_concurrency: usize,
+    extraction_concurrency: usize,
+}
+
+async fn get_scheduler_stats(State(state): State<Arc<AppState>>) -> Json<SchedulerStats> {
+    Json(SchedulerStats {
+        llm_concurrency: state.scheduler.effective_llm_concurrency(),
+        extraction_concurrency: state.scheduler.effective_extraction_concurrency(),
+    })
+}
+
 async fn clear_cache(
     State(state): State<Arc<AppState>>,
 ) -> StatusCode {
@@ -265,59 +367,249 @@ async fn health_check(
 }
 
 async fn ingest_document(
+    State(state): State<Arc<AppState>>,
     Json(req): Json<IngestRequest>,
-) -> Result<Json<IngestResponse>, StatusCode> {
+) -> Result<Json<EnqueuedResponse>, AppError> {
+    let request_payload = serde_json::to_value(&req).map_err(|e| AppError::Internal(e.to_string()))?;
+    let task_uid = state
+        .task_controller
+        .enqueue(TaskKind::Ingest(req), request_payload)
+        .await?;
+
+    Ok(Json(EnqueuedResponse {
+        task_uid,
+        status: "enqueued",
+    }))
+}
+
+async fn run_ingest(
+    state: &Arc<AppState>,
+    req: IngestRequest,
+) -> Result<IngestResponse, AppError> {
+    // An `s3://bucket/prefix` path reads from the S3-compatible bucket
+    // configured in `config.s3` instead of the local filesystem.
+    if let Some(rest) = req.path.strip_prefix("s3://") {
+        let s3_config = state.config.s3.as_ref().ok_or_else(|| {
+            AppError::InvalidPath(
+                "path is an s3:// URI but no s3 config is set (GRAPHRAG_S3__* or config file s3 section)".to_string(),
+            )
+        })?;
+        let (bucket, prefix) = rest.split_once('/').unwrap_or((rest, ""));
+        let store = ingest::S3Store::new(ingest::S3StoreConfig {
+            endpoint: s3_config.endpoint.clone(),
+            region: s3_config.region.clone(),
+            bucket: bucket.to_string(),
+            access_key: s3_config.access_key.clone(),
+            secret_key: s3_config.secret_key.clone(),
+            prefix: prefix.to_string(),
+        });
+        let chunks = ingest::ingest_source(&store)
+            .await
+            .map_err(|e| AppError::Internal(e.to_string()))?;
+
+        return write_ingested_chunks(chunks, Vec::new()).await;
+    }
+
     let path = PathBuf::from(&req.path);
-    
+
     if !path.exists() {
-        return Err(StatusCode::NOT_FOUND);
+        return Err(AppError::PathNotFound(req.path.clone()));
     }
-    
-    let chunks = if path.is_file() {
-        ingest::ingest_file(&path)
+
+    // `OperationMode::Incremental` diffs the directory against the chunk-ID
+    // manifest from the last ingest, so only added chunks are (re-)written
+    // and a long-running corpus doesn't get fully re-embedded every run.
+    let incremental = state.config.mode == config::OperationMode::Incremental && path.is_dir();
+
+    let (chunks, removed_chunk_ids) = if incremental {
+        let manifest_path = PathBuf::from(&state.config.incremental.manifest_path);
+        let diff = ingest::ingest_directory_incremental(&path, &manifest_path)
             .await
-            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+            .map_err(|e| AppError::Internal(e.to_string()))?;
+        (diff.added, diff.removed)
+    } else if path.is_file() {
+        (
+            ingest::ingest_file(&path)
+                .await
+                .map_err(|e| AppError::Internal(e.to_string()))?,
+            Vec::new(),
+        )
     } else if path.is_dir() {
-        ingest::ingest_directory(&path)
-            .await
-            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        (
+            ingest::ingest_directory(&path)
+                .await
+                .map_err(|e| AppError::Internal(e.to_string()))?,
+            Vec::new(),
+        )
     } else {
-        return Err(StatusCode::BAD_REQUEST);
+        return Err(AppError::InvalidPath(req.path.clone()));
     };
-    
-    // Save chunks to disk (data/chunks/)
+
+    write_ingested_chunks(chunks, removed_chunk_ids).await
+}
+
+/// Shared tail of `run_ingest`: persist freshly ingested chunks to
+/// `data/chunks/`, drop any the manifest diff found gone from the corpus,
+/// and summarize the result.
+async fn write_ingested_chunks(
+    chunks: Vec<ingest::Chunk>,
+    removed_chunk_ids: Vec<String>,
+) -> Result<IngestResponse, AppError> {
     let output_dir = PathBuf::from("data/chunks");
     tokio::fs::create_dir_all(&output_dir)
         .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    
+        .map_err(|e| AppError::Internal(e.to_string()))?;
+
+    // Chunks the manifest diff found gone from the corpus no longer have
+    // anything to extract from; drop their stale chunk files too.
+    for chunk_id in &removed_chunk_ids {
+        let chunk_file = output_dir.join(format!("{}.json", chunk_id));
+        let _ = tokio::fs::remove_file(chunk_file).await;
+    }
+
     let mut doc_ids = std::collections::HashSet::new();
-    
+
     for chunk in &chunks {
         doc_ids.insert(chunk.doc_id.clone());
-        
+
         let chunk_file = output_dir.join(format!("{}.json", chunk.chunk_id));
         let json = serde_json::to_string_pretty(chunk)
-            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+            .map_err(|e| AppError::Internal(e.to_string()))?;
         tokio::fs::write(chunk_file, json)
             .await
-            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+            .map_err(|e| AppError::Internal(e.to_string()))?;
     }
-    
-    Ok(Json(IngestResponse {
+
+    Ok(IngestResponse {
         chunks_created: chunks.len(),
         doc_ids: doc_ids.into_iter().collect(),
+    })
+}
+
+/// Accepts one or more uploaded files directly in the request body, so
+/// clients that don't share the server's filesystem can still ingest
+/// documents. Each file is streamed to a temp file (preserving its
+/// original filename) and run through the same `ingest::ingest_file` path
+/// as `/ingest`. An optional `doc_id` text field, if present before the
+/// file fields, overrides the generated doc id for every file that
+/// follows it.
+async fn ingest_upload(mut multipart: Multipart) -> Result<Json<IngestResponse>, AppError> {
+    let output_dir = PathBuf::from("data/chunks");
+    tokio::fs::create_dir_all(&output_dir)
+        .await
+        .map_err(|e| AppError::Internal(e.to_string()))?;
+
+    let mut doc_id_override: Option<String> = None;
+    let mut all_chunks: Vec<ingest::Chunk> = Vec::new();
+    let mut field_index: u32 = 0;
+
+    while let Some(mut field) = multipart
+        .next_field()
+        .await
+        .map_err(|e| AppError::InvalidPath(e.to_string()))?
+    {
+        if field.name() == Some("doc_id") {
+            doc_id_override = Some(
+                field
+                    .text()
+                    .await
+                    .map_err(|e| AppError::InvalidPath(e.to_string()))?,
+            );
+            continue;
+        }
+
+        let file_name = field
+            .file_name()
+            .map(|s| s.to_string())
+            .unwrap_or_else(|| "upload".to_string());
+
+        let extension = PathBuf::from(&file_name)
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or("")
+            .to_string();
+
+        if !ingest::reader::supported_extension(&extension.to_lowercase()) {
+            return Err(AppError::UnsupportedFileType(extension));
+        }
+
+        field_index += 1;
+        let temp_path = std::env::temp_dir().join(format!(
+            "graphrag-upload-{}-{}-{}",
+            std::process::id(),
+            field_index,
+            file_name
+        ));
+
+        let io_err = |e: std::io::Error| AppError::Internal(e.to_string());
+        let mut temp_file = tokio::fs::File::create(&temp_path).await.map_err(io_err)?;
+        while let Some(bytes) = field
+            .chunk()
+            .await
+            .map_err(|e| AppError::Internal(e.to_string()))?
+        {
+            tokio::io::AsyncWriteExt::write_all(&mut temp_file, &bytes).await.map_err(io_err)?;
+        }
+        tokio::io::AsyncWriteExt::flush(&mut temp_file).await.map_err(io_err)?;
+        drop(temp_file);
+
+        let ingest_result = ingest::ingest_file(&temp_path).await;
+        let _ = tokio::fs::remove_file(&temp_path).await;
+        let mut chunks = ingest_result.map_err(|e| AppError::Internal(e.to_string()))?;
+
+        if let Some(doc_id) = &doc_id_override {
+            for chunk in &mut chunks {
+                chunk.doc_id = doc_id.clone();
+            }
+        }
+
+        all_chunks.extend(chunks);
+    }
+
+    let mut doc_ids = std::collections::HashSet::new();
+    for chunk in &all_chunks {
+        doc_ids.insert(chunk.doc_id.clone());
+
+        let chunk_file = output_dir.join(format!("{}.json", chunk.chunk_id));
+        let json = serde_json::to_string_pretty(chunk)
+            .map_err(|e| AppError::Internal(e.to_string()))?;
+        tokio::fs::write(chunk_file, json)
+            .await
+            .map_err(|e| AppError::Internal(e.to_string()))?;
+    }
+
+    Ok(Json(IngestResponse {
+        chunks_created: all_chunks.len(),
+        doc_ids: doc_ids.into_iter().collect(),
     }))
 }
 
 async fn extract_chunks(
     State(state): State<Arc<AppState>>,
     req: Option<Json<ExtractRequest>>,
-) -> Result<Json<ExtractResponse>, StatusCode> {
+) -> Result<Json<EnqueuedResponse>, AppError> {
+    let req = req.map(|Json(req)| req);
+    let request_payload =
+        serde_json::to_value(&req).map_err(|e| AppError::Internal(e.to_string()))?;
+    let task_uid = state
+        .task_controller
+        .enqueue(TaskKind::Extract(req), request_payload)
+        .await?;
+
+    Ok(Json(EnqueuedResponse {
+        task_uid,
+        status: "enqueued",
+    }))
+}
+
+async fn run_extract(
+    state: &Arc<AppState>,
+    req: Option<ExtractRequest>,
+) -> Result<ExtractResponse, AppError> {
     let chunks_dir = PathBuf::from("data/chunks");
-    
+
     // Read chunk files
-    let chunk_files: Vec<PathBuf> = if let Some(Json(req)) = req {
+    let chunk_files: Vec<PathBuf> = if let Some(req) = req {
         if let Some(chunk_file) = req.chunk_file {
             vec![chunks_dir.join(chunk_file)]
         } else {
@@ -332,27 +624,58 @@ async fn extract_chunks(
     let output_dir = PathBuf::from("data/extracted");
     tokio::fs::create_dir_all(&output_dir)
         .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        .map_err(|e| AppError::Internal(e.to_string()))?;
+
+    // `OperationMode::Incremental` tracks per-chunk progress in a checkpoint
+    // file so a crash mid-corpus resumes instead of reprocessing everything.
+    let incremental = state.config.mode == config::OperationMode::Incremental;
+    let checkpoint_path = PathBuf::from(&state.config.incremental.checkpoint_path);
+    let mut checkpoint = if incremental {
+        ingest::Checkpoint::load(&checkpoint_path)
+            .await
+            .map_err(|e| AppError::Internal(e.to_string()))?
+    } else {
+        ingest::Checkpoint::default()
+    };
 
     for chunk_path in &chunk_files {
         // Read chunk
         let chunk_json = tokio::fs::read_to_string(chunk_path)
             .await
-            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-        
+            .map_err(|_| AppError::ChunkNotFound(chunk_path.display().to_string()))?;
+
         let chunk: ingest::Chunk = serde_json::from_str(&chunk_json)
-            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+            .map_err(|e| AppError::BadChunkJson(e.to_string()))?;
+
+        let checkpoint_key = if state.config.incremental.content_hashing {
+            chunk.content_checksum.clone()
+        } else {
+            chunk.chunk_id.clone()
+        };
+
+        if incremental && checkpoint.is_completed(&checkpoint_key) {
+            continue;
+        }
+
+        if incremental {
+            checkpoint.mark_in_flight(&checkpoint_key);
+            checkpoint
+                .save(&checkpoint_path)
+                .await
+                .map_err(|e| AppError::Internal(e.to_string()))?;
+        }
 
         // Extract entities and relations
         let mut extractor = state.extractor.lock().await;
-        
+
         let extracted = extractor
             .extract_chunk(chunk.chunk_id.clone(), chunk.doc_id.clone(), &chunk.text)
             .await
             .map_err(|e| {
                 eprintln!("Extraction error: {}", e);
-                StatusCode::INTERNAL_SERVER_ERROR
+                AppError::ExtractionFailed(e.to_string())
             })?;
+        drop(extractor);
 
         total_entities += extracted.extraction.entities.len();
         total_relations += extracted.extraction.relations.len();
@@ -360,29 +683,37 @@ async fn extract_chunks(
         // Save extracted data
         let output_file = output_dir.join(format!("{}.json", chunk.chunk_id));
         let json = serde_json::to_string_pretty(&extracted)
-            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+            .map_err(|e| AppError::Internal(e.to_string()))?;
         tokio::fs::write(output_file, json)
             .await
-            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+            .map_err(|e| AppError::Internal(e.to_string()))?;
+
+        if incremental {
+            checkpoint.mark_completed(&checkpoint_key);
+            checkpoint
+                .save(&checkpoint_path)
+                .await
+                .map_err(|e| AppError::Internal(e.to_string()))?;
+        }
     }
 
-    Ok(Json(ExtractResponse {
+    Ok(ExtractResponse {
         chunks_processed: chunk_files.len(),
         entities_extracted: total_entities,
         relations_extracted: total_relations,
-    }))
+    })
 }
 
 // Helper function to read chunk files from directory
-async fn read_chunk_files(chunks_dir: &PathBuf) -> Result<Vec<PathBuf>, StatusCode> {
+async fn read_chunk_files(chunks_dir: &PathBuf) -> Result<Vec<PathBuf>, AppError> {
     let mut entries = tokio::fs::read_dir(&chunks_dir)
         .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    
+        .map_err(|e| AppError::Internal(e.to_string()))?;
+
     let mut files = Vec::new();
     while let Some(entry) = entries.next_entry()
         .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)? 
+        .map_err(|e| AppError::Internal(e.to_string()))?
     {
         let path = entry.path();
         if path.is_file() {
@@ -405,22 +736,32 @@ struct IndexResponse {
 
 async fn index_data(
     State(state): State<Arc<AppState>>,
-) -> Result<Json<IndexResponse>, StatusCode> {
+) -> Result<Json<EnqueuedResponse>, AppError> {
+    let task_uid = state
+        .task_controller
+        .enqueue(TaskKind::Index, serde_json::Value::Null)
+        .await?;
+
+    Ok(Json(EnqueuedResponse {
+        task_uid,
+        status: "enqueued",
+    }))
+}
+
+async fn run_index(state: &Arc<AppState>) -> Result<IndexResponse, AppError> {
     let chunks_dir = PathBuf::from("data/chunks");
     let extracted_dir = PathBuf::from("data/extracted");
 
     // Read all extracted files
     let mut entries = tokio::fs::read_dir(&extracted_dir)
         .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        .map_err(|e| AppError::Internal(e.to_string()))?;
 
-    let mut chunks_indexed = 0;
-    let mut total_entities = 0;
-    let mut total_relations = 0;
+    let mut items: Vec<(ingest::Chunk, extract::ExtractedChunk)> = Vec::new();
 
     while let Some(entry) = entries.next_entry()
         .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .map_err(|e| AppError::Internal(e.to_string()))?
     {
         let path = entry.path();
         if path.extension().and_then(|e| e.to_str()) != Some("json") {
@@ -430,52 +771,69 @@ async fn index_data(
         // Read extracted data
         let extracted_json = tokio::fs::read_to_string(&path)
             .await
-            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-        
+            .map_err(|e| AppError::Internal(e.to_string()))?;
+
         let extracted: extract::ExtractedChunk = serde_json::from_str(&extracted_json)
-            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+            .map_err(|e| AppError::BadChunkJson(e.to_string()))?;
 
         // Read corresponding chunk
         let chunk_file = chunks_dir.join(format!("{}.json", extracted.chunk_id));
         let chunk_json = tokio::fs::read_to_string(&chunk_file)
             .await
-            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-        
+            .map_err(|_| AppError::ChunkNotFound(chunk_file.display().to_string()))?;
+
         let chunk: ingest::Chunk = serde_json::from_str(&chunk_json)
-            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+            .map_err(|e| AppError::BadChunkJson(e.to_string()))?;
 
-        // Index both
-        state.indexer
-            .index_extracted_chunk(&chunk, &extracted)
-            .await
-            .map_err(|e| {
-                eprintln!("Indexing error: {}", e);
-                StatusCode::INTERNAL_SERVER_ERROR
-            })?;
+        items.push((chunk, extracted));
+    }
 
-        chunks_indexed += 1;
-        total_entities += extracted.extraction.entities.len();
-        total_relations += extracted.extraction.relations.len();
+    let total_entities: usize = items.iter().map(|(_, e)| e.extraction.entities.len()).sum();
+    let total_relations: usize = items.iter().map(|(_, e)| e.extraction.relations.len()).sum();
+
+    // Index the whole batch at once (concurrent Qdrant/Neo4j halves, bounded
+    // internal concurrency) instead of one chunk at a time.
+    let timer = TimedOperation::start();
+    let results = state.indexer
+        .index_batch(&items)
+        .await
+        .map_err(|e| {
+            eprintln!("Indexing error: {}", e);
+            AppError::QdrantUnavailable(e.to_string())
+        })?;
+    state.metrics.record_index(timer.elapsed());
+
+    if let Some(failed) = results.iter().find(|r| !r.success) {
+        return Err(AppError::QdrantUnavailable(
+            failed.error.clone().unwrap_or_else(|| "unknown indexing error".to_string()),
+        ));
     }
 
-    Ok(Json(IndexResponse {
-        chunks_indexed,
+    Ok(IndexResponse {
+        chunks_indexed: results.len(),
         entities_indexed: total_entities,
         relations_indexed: total_relations,
-    }))
+    })
 }
 
 async fn get_stats(
     State(state): State<Arc<AppState>>,
-) -> Result<Json<index::IndexStats>, StatusCode> {
+) -> Result<Json<index::IndexStats>, AppError> {
     let stats = state.indexer
         .get_stats()
         .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    
+        .map_err(|e| AppError::QdrantUnavailable(e.to_string()))?;
+
     Ok(Json(stats))
 }
 
+#[derive(Deserialize, Serialize, Clone)]
+struct CommunitiesRequest {
+    /// Louvain resolution: above `1.0` favors many small communities, below
+    /// `1.0` favors fewer, larger ones. Defaults to `1.0`.
+    resolution: Option<f64>,
+}
+
 #[derive(Serialize)]
 struct CommunitiesResponse {
     communities_detected: usize,
@@ -484,36 +842,132 @@ struct CommunitiesResponse {
 
 async fn detect_communities(
     State(state): State<Arc<AppState>>,
-) -> Result<Json<CommunitiesResponse>, StatusCode> {
+    req: Option<Json<CommunitiesRequest>>,
+) -> Result<Json<EnqueuedResponse>, AppError> {
+    let req = req.map(|Json(req)| req);
+    let request_payload =
+        serde_json::to_value(&req).map_err(|e| AppError::Internal(e.to_string()))?;
+    let task_uid = state
+        .task_controller
+        .enqueue(TaskKind::Communities(req), request_payload)
+        .await?;
+
+    Ok(Json(EnqueuedResponse {
+        task_uid,
+        status: "enqueued",
+    }))
+}
+
+async fn run_communities(
+    state: &Arc<AppState>,
+    req: Option<CommunitiesRequest>,
+) -> Result<CommunitiesResponse, AppError> {
+    let resolution = req.and_then(|req| req.resolution).unwrap_or(1.0);
+
     let summaries = state.community_detector
-        .detect_and_summarize()
+        .detect_and_summarize(resolution)
         .await
         .map_err(|e| {
             eprintln!("Community detection error: {}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
+            AppError::Neo4jUnavailable(e.to_string())
         })?;
 
     // Save summaries to disk
     let output_dir = PathBuf::from("data/communities");
     tokio::fs::create_dir_all(&output_dir)
         .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        .map_err(|e| AppError::Internal(e.to_string()))?;
 
     for summary in &summaries {
-        let file_path = output_dir.join(format!("community_{}.json", summary.community_id));
+        let file_path = output_dir.join(format!(
+            "community_L{}_{}.json",
+            summary.level, summary.community_id
+        ));
         let json = serde_json::to_string_pretty(summary)
-            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+            .map_err(|e| AppError::Internal(e.to_string()))?;
         tokio::fs::write(file_path, json)
             .await
-            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+            .map_err(|e| AppError::Internal(e.to_string()))?;
     }
 
-    Ok(Json(CommunitiesResponse {
+    Ok(CommunitiesResponse {
         communities_detected: summaries.len(),
         summaries,
+    })
+}
+
+async fn run_benchmark_endpoint(
+    State(state): State<Arc<AppState>>,
+    req: Option<Json<BenchmarkRequest>>,
+) -> Result<Json<EnqueuedResponse>, AppError> {
+    let req = req.map(|Json(req)| req);
+    let request_payload =
+        serde_json::to_value(&req).map_err(|e| AppError::Internal(e.to_string()))?;
+    let task_uid = state
+        .task_controller
+        .enqueue(TaskKind::Benchmark(req), request_payload)
+        .await?;
+
+    Ok(Json(EnqueuedResponse {
+        task_uid,
+        status: "enqueued",
     }))
 }
 
+async fn run_benchmark(
+    state: &Arc<AppState>,
+    req: Option<BenchmarkRequest>,
+) -> Result<eval::BenchmarkResults, AppError> {
+    let req = req.unwrap_or(BenchmarkRequest {
+        test_set: None,
+        top_k: None,
+    });
+
+    match req.test_set.as_deref() {
+        None | Some("default") => {}
+        Some(other) => {
+            return Err(AppError::InvalidPath(format!(
+                "unknown benchmark test set '{}'",
+                other
+            )))
+        }
+    }
+    let top_k = req.top_k.unwrap_or(5);
+
+    let embedding_client = index::EmbeddingClient::default().with_retry_config(common::retry::RetryConfig {
+        max_retries: state.config.retry.max_retries,
+        initial_backoff_ms: state.config.retry.initial_backoff_ms,
+        max_backoff_ms: state.config.retry.max_backoff_ms,
+    });
+    let llm = query::QueryLLM::default().with_retry_config(common::retry::RetryConfig {
+        max_retries: state.config.retry.max_retries,
+        initial_backoff_ms: state.config.retry.initial_backoff_ms,
+        max_backoff_ms: state.config.retry.max_backoff_ms,
+    });
+    let vanilla_rag = eval::VanillaRAG::new(
+        Box::new(embedding_client),
+        llm,
+        "http://localhost:6333".to_string(),
+        "graphrag_chunks".to_string(),
+    );
+    let benchmarker = eval::Benchmarker::new(vanilla_rag, "http://localhost:3000".to_string());
+
+    let test_set = eval::get_test_set();
+    let results = benchmarker
+        .run_benchmark(&test_set, top_k)
+        .await
+        .map_err(|e| AppError::Internal(format!("Benchmark run failed: {}", e)))?;
+
+    // Plot generation is a nice-to-have; don't fail the whole benchmark if
+    // the plotting backend can't write to disk.
+    let plot_dir = "data/benchmarks";
+    if let Err(e) = eval::generate_plots(&results, plot_dir) {
+        tracing::warn!(error = %e, "Failed to generate benchmark plots");
+    }
+
+    Ok(results)
+}
+
 #[derive(Deserialize)]
 struct QueryRequest {
     query: String,
@@ -529,7 +983,7 @@ async fn query_local(
     State(state): State<Arc<AppState>>,
     axum::Extension(request_id): axum::Extension<RequestId>,
     Json(req): Json<QueryRequest>,
-) -> Result<Json<query::LocalSearchResult>, StatusCode> {
+) -> Result<Json<query::LocalSearchResult>, AppError> {
     tracing::info!(
         request_id = %request_id.0,
         query = %req.query,
@@ -537,9 +991,10 @@ async fn query_local(
     );
 
     let timer = TimedOperation::start();
-    
-    let result = state.local_search
-        .search(&req.query, req.top_k)
+
+    let result = state
+        .scheduler
+        .run_llm(state.local_search.search(&req.query, req.top_k))
         .await
         .map_err(|e| {
             tracing::error!(
@@ -548,7 +1003,10 @@ async fn query_local(
                 "Local search failed"
             );
             state.metrics.record_request(false);
-            StatusCode::INTERNAL_SERVER_ERROR
+            match e {
+                SchedulerError::Timeout => AppError::LlmFailure("local search timed out".to_string()),
+                SchedulerError::Inner(e) => AppError::LlmFailure(e.to_string()),
+            }
         })?;
 
     state.metrics.record_query(timer.elapsed());
@@ -567,14 +1025,58 @@ async fn query_local(
 async fn query_global(
     State(state): State<Arc<AppState>>,
     Json(req): Json<QueryRequest>,
-) -> Result<Json<query::GlobalSearchResult>, StatusCode> {
-    let result = state.global_search
-        .search(&req.query, req.top_k)
+) -> Result<Json<query::GlobalSearchResult>, AppError> {
+    let result = state
+        .scheduler
+        .run_llm(state.global_search.search(&req.query, req.top_k))
         .await
         .map_err(|e| {
             eprintln!("Global search error: {}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
+            match e {
+                SchedulerError::Timeout => AppError::LlmFailure("global search timed out".to_string()),
+                SchedulerError::Inner(e) => AppError::LlmFailure(e.to_string()),
+            }
         })?;
 
     Ok(Json(result))
+}
+
+#[derive(Deserialize)]
+struct TaskListQuery {
+    status: Option<String>,
+}
+
+async fn get_task(
+    State(state): State<Arc<AppState>>,
+    Path(uid): Path<u64>,
+) -> Result<Json<TaskInfo>, AppError> {
+    state
+        .task_store
+        .get(uid)
+        .map(Json)
+        .ok_or(AppError::TaskNotFound(uid))
+}
+
+async fn list_tasks(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<TaskListQuery>,
+) -> Result<Json<Vec<TaskInfo>>, AppError> {
+    let status = match params.status.as_deref() {
+        None => None,
+        Some("enqueued") => Some(TaskStatus::Enqueued),
+        Some("processing") => Some(TaskStatus::Processing),
+        Some("succeeded") => Some(TaskStatus::Succeeded),
+        Some("failed") => Some(TaskStatus::Failed),
+        Some(other) => return Err(AppError::InvalidPath(format!("unknown task status '{}'", other))),
+    };
+
+    Ok(Json(state.task_store.list(status)))
+}
+
+async fn search_chunks(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<SearchParams>,
+) -> Result<Json<SearchResponse>, AppError> {
+    let result = search::run_search(&state, params).await?;
+    Ok(Json(result))
 }
\ No newline at end of file
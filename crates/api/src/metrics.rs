@@ -1,19 +1,110 @@
 use serde::Serialize;
+use std::fmt::Write as _;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use std::time::Instant;
 
+/// Upper bound (in milliseconds) of each histogram bucket. The last bucket
+/// implicitly has an unbounded `+Inf` counterpart added at render time.
+const BUCKET_BOUNDARIES_MS: [f64; 8] = [1.0, 5.0, 10.0, 50.0, 100.0, 500.0, 1000.0, 5000.0];
+
+/// A fixed-bucket histogram: lock-free via plain `AtomicU64` counters, one
+/// per finite bucket boundary. `observe` bumps at most one bucket (none for
+/// an observation past the last boundary, which only shows up in `+Inf` via
+/// the overall count); `cumulative_counts` accumulates them into the
+/// cumulative counts Prometheus/OpenMetrics histograms expect at render
+/// time, so the hot path never pays for more than one atomic increment.
+struct Histogram {
+    buckets: [AtomicU64; BUCKET_BOUNDARIES_MS.len()],
+    sum_us: AtomicU64,
+    count: AtomicU64,
+}
+
+impl Histogram {
+    fn new() -> Self {
+        Self {
+            buckets: std::array::from_fn(|_| AtomicU64::new(0)),
+            sum_us: AtomicU64::new(0),
+            count: AtomicU64::new(0),
+        }
+    }
+
+    /// Observations past the last finite boundary fall into the implicit
+    /// `+Inf` bucket: no counter to bump, but they still count toward the
+    /// overall `count`/`sum`, which is all `+Inf`'s cumulative count needs.
+    fn observe(&self, duration: std::time::Duration) {
+        let ms = duration.as_secs_f64() * 1000.0;
+        if let Some(idx) = BUCKET_BOUNDARIES_MS.iter().position(|&boundary| ms <= boundary) {
+            self.buckets[idx].fetch_add(1, Ordering::Relaxed);
+        }
+        self.sum_us.fetch_add(duration.as_micros() as u64, Ordering::Relaxed);
+        self.count.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Cumulative observation count at each boundary in `BUCKET_BOUNDARIES_MS`
+    /// (i.e. `buckets[i]` is how many observations were `<= BUCKET_BOUNDARIES_MS[i]`).
+    fn cumulative_counts(&self) -> [u64; BUCKET_BOUNDARIES_MS.len()] {
+        let mut cumulative = [0u64; BUCKET_BOUNDARIES_MS.len()];
+        let mut running = 0u64;
+        for (i, bucket) in self.buckets.iter().enumerate() {
+            running += bucket.load(Ordering::Relaxed);
+            cumulative[i] = running;
+        }
+        cumulative
+    }
+
+    fn count(&self) -> u64 {
+        self.count.load(Ordering::Relaxed)
+    }
+
+    fn sum_seconds(&self) -> f64 {
+        self.sum_us.load(Ordering::Relaxed) as f64 / 1_000_000.0
+    }
+
+    fn avg_ms(&self) -> f64 {
+        let count = self.count();
+        if count == 0 {
+            0.0
+        } else {
+            self.sum_seconds() * 1000.0 / count as f64
+        }
+    }
+}
+
+/// Render `histogram` as an OpenMetrics histogram named `name` into `buf`.
+fn render_histogram(buf: &mut String, name: &str, help: &str, histogram: &Histogram) {
+    let _ = writeln!(buf, "# HELP {name} {help}");
+    let _ = writeln!(buf, "# TYPE {name} histogram");
+
+    let cumulative = histogram.cumulative_counts();
+    for (boundary_ms, count) in BUCKET_BOUNDARIES_MS.iter().zip(cumulative.iter()) {
+        let boundary_seconds = boundary_ms / 1000.0;
+        let _ = writeln!(buf, "{name}_bucket{{le=\"{boundary_seconds}\"}} {count}");
+    }
+    let total = histogram.count();
+    let _ = writeln!(buf, "{name}_bucket{{le=\"+Inf\"}} {total}");
+    let _ = writeln!(buf, "{name}_sum {:.6}", histogram.sum_seconds());
+    let _ = writeln!(buf, "{name}_count {total}");
+}
+
+/// Render a single counter named `name` into `buf`.
+fn render_counter(buf: &mut String, name: &str, help: &str, value: usize) {
+    let _ = writeln!(buf, "# HELP {name} {help}");
+    let _ = writeln!(buf, "# TYPE {name} counter");
+    let _ = writeln!(buf, "{name} {value}");
+}
+
 pub struct Metrics {
     // Counters
     total_requests: AtomicUsize,
     successful_requests: AtomicUsize,
     failed_requests: AtomicUsize,
 
-    // Timing (in microseconds)
-    total_ingest_time_us: AtomicU64,
-    total_extract_time_us: AtomicU64,
-    total_index_time_us: AtomicU64,
-    total_query_time_us: AtomicU64,
+    // Timing histograms, one per pipeline stage
+    ingest_duration: Histogram,
+    extract_duration: Histogram,
+    index_duration: Histogram,
+    query_duration: Histogram,
 
     // Counts
     total_chunks_processed: AtomicUsize,
@@ -26,10 +117,10 @@ impl Metrics {
             total_requests: AtomicUsize::new(0),
             successful_requests: AtomicUsize::new(0),
             failed_requests: AtomicUsize::new(0),
-            total_ingest_time_us: AtomicU64::new(0),
-            total_extract_time_us: AtomicU64::new(0),
-            total_index_time_us: AtomicU64::new(0),
-            total_query_time_us: AtomicU64::new(0),
+            ingest_duration: Histogram::new(),
+            extract_duration: Histogram::new(),
+            index_duration: Histogram::new(),
+            query_duration: Histogram::new(),
             total_chunks_processed: AtomicUsize::new(0),
             total_entities_extracted: AtomicUsize::new(0),
         })
@@ -45,21 +136,21 @@ impl Metrics {
     }
 
     pub fn record_ingest(&self, duration: std::time::Duration, chunks: usize) {
-        self.total_ingest_time_us.fetch_add(duration.as_micros() as u64, Ordering::Relaxed);
+        self.ingest_duration.observe(duration);
         self.total_chunks_processed.fetch_add(chunks, Ordering::Relaxed);
     }
 
     pub fn record_extract(&self, duration: std::time::Duration, entities: usize) {
-        self.total_extract_time_us.fetch_add(duration.as_micros() as u64, Ordering::Relaxed);
+        self.extract_duration.observe(duration);
         self.total_entities_extracted.fetch_add(entities, Ordering::Relaxed);
     }
 
     pub fn record_index(&self, duration: std::time::Duration) {
-        self.total_index_time_us.fetch_add(duration.as_micros() as u64, Ordering::Relaxed);
+        self.index_duration.observe(duration);
     }
 
     pub fn record_query(&self, duration: std::time::Duration) {
-        self.total_query_time_us.fetch_add(duration.as_micros() as u64, Ordering::Relaxed);
+        self.query_duration.observe(duration);
     }
 
     pub fn snapshot(&self) -> MetricsSnapshot {
@@ -67,23 +158,79 @@ impl Metrics {
             total_requests: self.total_requests.load(Ordering::Relaxed),
             successful_requests: self.successful_requests.load(Ordering::Relaxed),
             failed_requests: self.failed_requests.load(Ordering::Relaxed),
-            avg_ingest_time_ms: self.avg_time_ms(&self.total_ingest_time_us, &self.total_chunks_processed),
-            avg_extract_time_ms: self.avg_time_ms(&self.total_extract_time_us, &self.total_entities_extracted),
-            avg_index_time_ms: self.avg_time_ms(&self.total_index_time_us, &AtomicUsize::new(1)),
-            avg_query_time_ms: self.avg_time_ms(&self.total_query_time_us, &self.total_requests),
+            avg_ingest_time_ms: self.ingest_duration.avg_ms(),
+            avg_extract_time_ms: self.extract_duration.avg_ms(),
+            avg_index_time_ms: self.index_duration.avg_ms(),
+            avg_query_time_ms: self.query_duration.avg_ms(),
             total_chunks_processed: self.total_chunks_processed.load(Ordering::Relaxed),
             total_entities_extracted: self.total_entities_extracted.load(Ordering::Relaxed),
         }
     }
 
-    fn avg_time_ms(&self, total_us: &AtomicU64, count: &AtomicUsize) -> f64 {
-        let total = total_us.load(Ordering::Relaxed) as f64;
-        let cnt = count.load(Ordering::Relaxed) as f64;
-        if cnt > 0.0 {
-            total / cnt / 1000.0 // Convert to ms
-        } else {
-            0.0
-        }
+    /// Render all metrics in OpenMetrics text exposition format, suitable
+    /// for a Prometheus scrape target.
+    pub fn render_prometheus(&self) -> String {
+        let mut buf = String::new();
+
+        render_counter(
+            &mut buf,
+            "graphrag_requests_total",
+            "Total requests handled.",
+            self.total_requests.load(Ordering::Relaxed),
+        );
+        render_counter(
+            &mut buf,
+            "graphrag_requests_successful_total",
+            "Requests that completed successfully.",
+            self.successful_requests.load(Ordering::Relaxed),
+        );
+        render_counter(
+            &mut buf,
+            "graphrag_requests_failed_total",
+            "Requests that failed.",
+            self.failed_requests.load(Ordering::Relaxed),
+        );
+
+        render_histogram(
+            &mut buf,
+            "graphrag_ingest_duration_seconds",
+            "Time spent ingesting documents.",
+            &self.ingest_duration,
+        );
+        render_histogram(
+            &mut buf,
+            "graphrag_extract_duration_seconds",
+            "Time spent extracting entities and relations.",
+            &self.extract_duration,
+        );
+        render_histogram(
+            &mut buf,
+            "graphrag_index_duration_seconds",
+            "Time spent indexing chunks into Qdrant and Neo4j.",
+            &self.index_duration,
+        );
+        render_histogram(
+            &mut buf,
+            "graphrag_query_duration_seconds",
+            "Time spent answering a query.",
+            &self.query_duration,
+        );
+
+        render_counter(
+            &mut buf,
+            "graphrag_chunks_processed_total",
+            "Total chunks processed during ingest.",
+            self.total_chunks_processed.load(Ordering::Relaxed),
+        );
+        render_counter(
+            &mut buf,
+            "graphrag_entities_extracted_total",
+            "Total entities extracted.",
+            self.total_entities_extracted.load(Ordering::Relaxed),
+        );
+
+        buf.push_str("# EOF\n");
+        buf
     }
 }
 
@@ -114,4 +261,4 @@ impl TimedOperation {
     pub fn elapsed(&self) -> std::time::Duration {
         self.start.elapsed()
     }
-}
\ No newline at end of file
+}
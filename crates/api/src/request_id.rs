@@ -0,0 +1,22 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use axum::extract::Request;
+use axum::middleware::Next;
+use axum::response::Response;
+
+static NEXT_REQUEST_ID: AtomicU64 = AtomicU64::new(1);
+
+/// A per-request identifier, attached as a request extension so handlers
+/// can tag their tracing spans with something a caller can correlate
+/// against server logs.
+#[derive(Debug, Clone)]
+pub struct RequestId(pub String);
+
+/// Assigns every incoming request a `RequestId` before it reaches routing,
+/// so handlers (and any middleware layered after this one) can pull it out
+/// of the request extensions.
+pub async fn request_id_middleware(mut req: Request, next: Next) -> Response {
+    let id = NEXT_REQUEST_ID.fetch_add(1, Ordering::SeqCst);
+    req.extensions_mut().insert(RequestId(format!("req-{id:x}")));
+    next.run(req).await
+}
@@ -0,0 +1,311 @@
+#![allow(dead_code)]
+use std::collections::VecDeque;
+use std::future::Future;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::{Mutex, Semaphore};
+
+use crate::config::ConcurrencyConfig;
+
+/// How many outcomes a `Limiter` keeps to judge whether it should shrink.
+const WINDOW_SIZE: usize = 20;
+/// Shrink once more than this fraction of a full window is failures. Any
+/// error the wrapped future returns counts, as does a timeout; rate-limit
+/// errors are not told apart from other errors. Below `WINDOW_SIZE`
+/// outcomes recorded, we never shrink - a cold start shouldn't look like
+/// instability.
+const BAD_OUTCOME_RATIO: f64 = 0.3;
+/// Consecutive successes required before growing the permit count back by
+/// one step.
+const COOLDOWN_SUCCESSES: usize = 10;
+
+/// How a call through a `Limiter` turned out, for the rolling window that
+/// drives adaptive downshifting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Outcome {
+    Success,
+    Failed,
+}
+
+/// The error a `Scheduler::run_llm`/`run_extraction` call returns:
+/// either the wrapped future ran out of time, or it completed but
+/// returned its own error.
+#[derive(Debug)]
+pub enum SchedulerError<E> {
+    Timeout,
+    Inner(E),
+}
+
+impl<E: std::fmt::Display> std::fmt::Display for SchedulerError<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SchedulerError::Timeout => write!(f, "operation timed out"),
+            SchedulerError::Inner(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl<E: std::fmt::Debug + std::fmt::Display> std::error::Error for SchedulerError<E> {}
+
+/// A `tokio::sync::Semaphore`-backed concurrency limit that shrinks itself
+/// (additive-increase/multiplicative-decrease) when the calls passing
+/// through it start failing or timing out, and grows back toward
+/// `ceiling` once a cooldown streak of successes proves the backend has
+/// recovered. `effective()` reports the current target, which is what
+/// `ceiling` degrades to under pressure rather than staying fixed.
+struct Limiter {
+    semaphore: Arc<Semaphore>,
+    ceiling: usize,
+    floor: usize,
+    effective: AtomicUsize,
+    consecutive_successes: AtomicUsize,
+    outcomes: Mutex<VecDeque<Outcome>>,
+    timeout: Duration,
+}
+
+impl Limiter {
+    fn new(permits: usize, timeout: Duration) -> Self {
+        let permits = permits.max(1);
+        Self {
+            semaphore: Arc::new(Semaphore::new(permits)),
+            ceiling: permits,
+            floor: (permits / 4).max(1),
+            effective: AtomicUsize::new(permits),
+            consecutive_successes: AtomicUsize::new(0),
+            outcomes: Mutex::new(VecDeque::with_capacity(WINDOW_SIZE)),
+            timeout,
+        }
+    }
+
+    fn effective(&self) -> usize {
+        self.effective.load(Ordering::SeqCst)
+    }
+
+    async fn run<F, T, E>(&self, fut: F) -> Result<T, SchedulerError<E>>
+    where
+        F: Future<Output = Result<T, E>>,
+    {
+        let permit = self
+            .semaphore
+            .clone()
+            .acquire_owned()
+            .await
+            .expect("scheduler semaphore is never closed");
+
+        let result = match tokio::time::timeout(self.timeout, fut).await {
+            Ok(Ok(value)) => {
+                self.record(Outcome::Success).await;
+                Ok(value)
+            }
+            Ok(Err(e)) => {
+                self.record(Outcome::Failed).await;
+                Err(SchedulerError::Inner(e))
+            }
+            Err(_) => {
+                self.record(Outcome::Failed).await;
+                Err(SchedulerError::Timeout)
+            }
+        };
+
+        drop(permit);
+        result
+    }
+
+    async fn record(&self, outcome: Outcome) {
+        let mut window = self.outcomes.lock().await;
+        window.push_back(outcome);
+        if window.len() > WINDOW_SIZE {
+            window.pop_front();
+        }
+
+        match outcome {
+            Outcome::Success => {
+                let streak = self.consecutive_successes.fetch_add(1, Ordering::SeqCst) + 1;
+                if streak >= COOLDOWN_SUCCESSES {
+                    self.consecutive_successes.store(0, Ordering::SeqCst);
+                    self.grow();
+                }
+            }
+            Outcome::Failed => {
+                self.consecutive_successes.store(0, Ordering::SeqCst);
+                if window.len() == WINDOW_SIZE {
+                    let bad = window.iter().filter(|o| **o == Outcome::Failed).count();
+                    if bad as f64 / window.len() as f64 > BAD_OUTCOME_RATIO {
+                        drop(window);
+                        self.shrink();
+                    }
+                }
+            }
+        }
+    }
+
+    /// Multiplicative decrease: halve the effective permit count, down to
+    /// `floor`, by permanently forgetting the difference. A no-op if every
+    /// permit above the target is currently checked out - the next bad
+    /// outcome will try again.
+    fn shrink(&self) {
+        let current = self.effective.load(Ordering::SeqCst);
+        let target = (current / 2).max(self.floor);
+        if target >= current {
+            return;
+        }
+
+        let to_forget = current - target;
+        if let Ok(permit) = self.semaphore.try_acquire_many(to_forget as u32) {
+            permit.forget();
+            self.effective.store(target, Ordering::SeqCst);
+        }
+    }
+
+    /// Additive increase: grow the effective permit count by one quarter
+    /// of `ceiling` (at least one), never past it.
+    fn grow(&self) {
+        let current = self.effective.load(Ordering::SeqCst);
+        if current >= self.ceiling {
+            return;
+        }
+
+        let step = (self.ceiling / 4).max(1);
+        let target = (current + step).min(self.ceiling);
+        self.semaphore.add_permits(target - current);
+        self.effective.store(target, Ordering::SeqCst);
+    }
+}
+
+/// Enforces `ConcurrencyConfig` for real: LLM calls and extractions each
+/// run through their own adaptive permit pool plus a shared per-request
+/// timeout, so `OperationMode` presets bound how hard the crate can push
+/// a provider without needing every caller to remember to throttle
+/// itself.
+pub struct Scheduler {
+    llm: Limiter,
+    extraction: Limiter,
+}
+
+impl Scheduler {
+    pub fn new(cfg: &ConcurrencyConfig) -> Self {
+        let timeout = Duration::from_secs(cfg.request_timeout_secs);
+        Self {
+            llm: Limiter::new(cfg.max_concurrent_llm_calls, timeout),
+            extraction: Limiter::new(cfg.max_concurrent_extractions, timeout),
+        }
+    }
+
+    /// Run `fut` under the LLM-call permit pool and timeout.
+    pub async fn run_llm<F, T, E>(&self, fut: F) -> Result<T, SchedulerError<E>>
+    where
+        F: Future<Output = Result<T, E>>,
+    {
+        self.llm.run(fut).await
+    }
+
+    /// Run `fut` under the extraction permit pool and timeout.
+    pub async fn run_extraction<F, T, E>(&self, fut: F) -> Result<T, SchedulerError<E>>
+    where
+        F: Future<Output = Result<T, E>>,
+    {
+        self.extraction.run(fut).await
+    }
+
+    pub fn effective_llm_concurrency(&self) -> usize {
+        self.llm.effective()
+    }
+
+    pub fn effective_extraction_concurrency(&self) -> usize {
+        self.extraction.effective()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limiter(permits: usize) -> Limiter {
+        Limiter::new(permits, Duration::from_secs(60))
+    }
+
+    #[tokio::test]
+    async fn shrinks_once_the_window_is_full_of_mostly_bad_outcomes() {
+        let limiter = limiter(8);
+
+        // BAD_OUTCOME_RATIO is 0.3, so a window that's entirely failures
+        // crosses the threshold as soon as it fills up.
+        for _ in 0..WINDOW_SIZE {
+            limiter.record(Outcome::Failed).await;
+        }
+
+        // Multiplicative decrease: halves 8 -> 4.
+        assert_eq!(limiter.effective(), 4);
+    }
+
+    #[tokio::test]
+    async fn does_not_shrink_before_the_window_fills_up() {
+        let limiter = limiter(8);
+
+        for _ in 0..(WINDOW_SIZE - 1) {
+            limiter.record(Outcome::Failed).await;
+        }
+
+        // One outcome short of a full window - a cold start shouldn't
+        // already look unstable.
+        assert_eq!(limiter.effective(), 8);
+    }
+
+    #[tokio::test]
+    async fn does_not_shrink_when_failures_stay_under_the_ratio() {
+        let limiter = limiter(8);
+
+        // Fill the window first: the ratio check only runs once it's full,
+        // so the failures below need a full window behind them to actually
+        // exercise it rather than being skipped entirely.
+        for _ in 0..WINDOW_SIZE {
+            limiter.record(Outcome::Success).await;
+        }
+
+        // 5 of a full window is 25%, under BAD_OUTCOME_RATIO (30%).
+        for _ in 0..5 {
+            limiter.record(Outcome::Failed).await;
+        }
+
+        assert_eq!(limiter.effective(), 8);
+    }
+
+    #[tokio::test]
+    async fn grows_back_after_a_cooldown_streak_of_successes() {
+        let limiter = limiter(8);
+        limiter.shrink();
+        assert_eq!(limiter.effective(), 4);
+
+        for _ in 0..COOLDOWN_SUCCESSES {
+            limiter.record(Outcome::Success).await;
+        }
+
+        // Additive increase: one quarter of ceiling (8 / 4 = 2) past 4.
+        assert_eq!(limiter.effective(), 6);
+    }
+
+    #[tokio::test]
+    async fn never_grows_past_the_ceiling() {
+        let limiter = limiter(10);
+        limiter.shrink(); // 10 -> 5
+        limiter.grow(); // 5 -> 7
+        limiter.grow(); // 7 -> 9
+        limiter.grow(); // 9 + step(2) = 11, clamped down to the ceiling (10)
+        assert_eq!(limiter.effective(), 10);
+
+        limiter.grow(); // already at the ceiling - no-op
+        assert_eq!(limiter.effective(), 10);
+    }
+
+    #[tokio::test]
+    async fn never_shrinks_below_the_floor() {
+        let limiter = limiter(4);
+        // floor is max(4 / 4, 1) = 1.
+        limiter.shrink();
+        limiter.shrink();
+        limiter.shrink();
+        assert_eq!(limiter.effective(), 1);
+    }
+}
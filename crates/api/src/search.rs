@@ -0,0 +1,429 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use anyhow::Context;
+use index::Embedder;
+use neo4rs::Query;
+use serde::{Deserialize, Serialize};
+
+use crate::error::AppError;
+use crate::AppState;
+
+const QDRANT_URL: &str = "http://localhost:6333";
+const COLLECTION_NAME: &str = "graphrag_chunks";
+
+/// Hard ceiling on how many Qdrant candidates a filtered search will grow
+/// the fetch to before giving up and reporting whatever it has. Keeps a
+/// pathological filter (one that rejects almost everything) from turning a
+/// single `/search` call into an unbounded Qdrant scan.
+const MAX_CANDIDATE_FETCH: usize = 10_000;
+
+fn default_limit() -> usize {
+    20
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SearchParams {
+    /// Free-text term, embedded and matched via Qdrant similarity. Omit it
+    /// to fall back to a plain scroll over whatever `doc_id`/`entity`/
+    /// `relation_type` select.
+    pub q: Option<String>,
+    pub doc_id: Option<String>,
+    pub entity: Option<String>,
+    pub relation_type: Option<String>,
+    #[serde(default = "default_limit")]
+    pub limit: usize,
+    #[serde(default)]
+    pub offset: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SearchResponse {
+    pub total_matched: usize,
+    pub limit: usize,
+    pub offset: usize,
+    pub chunks: Vec<SearchChunkResult>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchChunkResult {
+    pub chunk_id: String,
+    pub doc_id: String,
+    pub source: String,
+    pub text: String,
+    pub score: Option<f32>,
+    pub entities: Vec<SearchEntity>,
+    pub relations: Vec<SearchRelation>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchEntity {
+    pub id: String,
+    pub name: String,
+    pub entity_type: String,
+    pub description: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchRelation {
+    pub source: String,
+    pub relation: String,
+    pub target: String,
+    pub evidence: String,
+}
+
+struct RawChunk {
+    chunk_id: String,
+    doc_id: String,
+    source: String,
+    text: String,
+    entity_ids: Vec<String>,
+    score: Option<f32>,
+}
+
+/// Deterministic, non-LLM retrieval over indexed chunks for `GET /search`.
+/// A `q` term runs a Qdrant similarity lookup; without one, this falls back
+/// to a plain scroll. `doc_id` is pushed down as a Qdrant payload filter.
+/// `entity`/`relation_type` are resolved against Neo4j first and then used
+/// to narrow the Qdrant results in-process, since a chunk's entity ids are
+/// stored in Qdrant payload as a flat comma-joined string rather than an
+/// indexed array Qdrant could filter on directly.
+///
+/// Because that narrowing happens after the Qdrant fetch, a `limit`/`offset`
+/// sized fetch would filter an already-capped window: `total_matched` would
+/// undercount, and paging past that window would silently miss real matches.
+/// When an `entity`/`relation_type` filter is active, the Qdrant fetch is
+/// grown (and re-filtered) until it covers `offset + limit` post-filter
+/// results, Qdrant runs dry, or `MAX_CANDIDATE_FETCH` is hit - whichever
+/// comes first - so filtering always happens over the full candidate set
+/// that was actually fetched.
+pub async fn run_search(
+    state: &Arc<AppState>,
+    params: SearchParams,
+) -> Result<SearchResponse, AppError> {
+    let client = reqwest::Client::new();
+
+    // Resolve the id filters once up front so the fetch-and-retry loop
+    // below can re-apply them to each larger batch of Qdrant candidates
+    // without re-querying Neo4j every iteration.
+    let entity_filter_ids = match params.entity.as_deref().filter(|e| !e.is_empty()) {
+        Some(entity) => Some(
+            entity_ids_matching_name(&state.neo4j_graph, entity)
+                .await
+                .map_err(|e| AppError::Neo4jUnavailable(e.to_string()))?,
+        ),
+        None => None,
+    };
+    let relation_filter_ids = match params.relation_type.as_deref().filter(|r| !r.is_empty()) {
+        Some(relation_type) => Some(
+            entity_ids_in_relation(&state.neo4j_graph, relation_type)
+                .await
+                .map_err(|e| AppError::Neo4jUnavailable(e.to_string()))?,
+        ),
+        None => None,
+    };
+    let has_post_filter = entity_filter_ids.is_some() || relation_filter_ids.is_some();
+
+    let query_embedding = match params.q.as_deref().filter(|q| !q.is_empty()) {
+        Some(q) => {
+            let embedding_client = index::EmbeddingClient::default().with_retry_config(common::retry::RetryConfig {
+                max_retries: state.config.retry.max_retries,
+                initial_backoff_ms: state.config.retry.initial_backoff_ms,
+                max_backoff_ms: state.config.retry.max_backoff_ms,
+            });
+            Some(
+                embedding_client
+                    .embed(q)
+                    .await
+                    .map_err(|e| AppError::Internal(format!("Failed to embed query: {}", e)))?,
+            )
+        }
+        None => None,
+    };
+
+    let needed = params.limit.saturating_add(params.offset).max(1);
+    let mut fetch_limit = needed;
+
+    let chunks = loop {
+        let mut candidates = match &query_embedding {
+            Some(embedding) => {
+                qdrant_vector_search(&client, embedding.clone(), params.doc_id.as_deref(), fetch_limit)
+                    .await
+                    .map_err(|e| AppError::QdrantUnavailable(e.to_string()))?
+            }
+            None => qdrant_scroll(&client, params.doc_id.as_deref(), fetch_limit)
+                .await
+                .map_err(|e| AppError::QdrantUnavailable(e.to_string()))?,
+        };
+        let fetched_count = candidates.len();
+
+        if let Some(ids) = &entity_filter_ids {
+            candidates.retain(|c| c.entity_ids.iter().any(|id| ids.contains(id)));
+        }
+        if let Some(ids) = &relation_filter_ids {
+            candidates.retain(|c| c.entity_ids.iter().any(|id| ids.contains(id)));
+        }
+
+        // Qdrant returned fewer than we asked for, so there's nothing left
+        // to grow into - this is every match there is.
+        let exhausted = fetched_count < fetch_limit;
+        let have_enough = !has_post_filter || candidates.len() >= needed;
+
+        if have_enough || exhausted || fetch_limit >= MAX_CANDIDATE_FETCH {
+            break candidates;
+        }
+
+        fetch_limit = (fetch_limit * 4).min(MAX_CANDIDATE_FETCH);
+    };
+
+    let total_matched = chunks.len();
+    let page: Vec<RawChunk> = chunks
+        .into_iter()
+        .skip(params.offset)
+        .take(params.limit)
+        .collect();
+
+    let all_entity_ids: HashSet<String> = page
+        .iter()
+        .flat_map(|c| c.entity_ids.iter().cloned())
+        .collect();
+
+    let (entity_details, relation_details) = if all_entity_ids.is_empty() {
+        (HashMap::new(), Vec::new())
+    } else {
+        let entities = entity_details_for(&state.neo4j_graph, &all_entity_ids)
+            .await
+            .map_err(|e| AppError::Neo4jUnavailable(e.to_string()))?;
+        let relations = relations_among(&state.neo4j_graph, &all_entity_ids)
+            .await
+            .map_err(|e| AppError::Neo4jUnavailable(e.to_string()))?;
+        (entities, relations)
+    };
+
+    let chunks = page
+        .into_iter()
+        .map(|c| {
+            let id_set: HashSet<String> = c.entity_ids.iter().cloned().collect();
+            let entities = c
+                .entity_ids
+                .iter()
+                .filter_map(|id| entity_details.get(id).cloned())
+                .collect();
+            let relations = relation_details
+                .iter()
+                .filter(|r| id_set.contains(&r.source) && id_set.contains(&r.target))
+                .cloned()
+                .collect();
+
+            SearchChunkResult {
+                chunk_id: c.chunk_id,
+                doc_id: c.doc_id,
+                source: c.source,
+                text: c.text,
+                score: c.score,
+                entities,
+                relations,
+            }
+        })
+        .collect();
+
+    Ok(SearchResponse {
+        total_matched,
+        limit: params.limit,
+        offset: params.offset,
+        chunks,
+    })
+}
+
+async fn qdrant_vector_search(
+    client: &reqwest::Client,
+    embedding: Vec<f32>,
+    doc_id: Option<&str>,
+    limit: usize,
+) -> anyhow::Result<Vec<RawChunk>> {
+    use serde_json::json;
+
+    let mut body = json!({
+        "vector": { "name": "dense", "vector": embedding },
+        "limit": limit,
+        "with_payload": true,
+    });
+    if let Some(doc_id) = doc_id {
+        body["filter"] = json!({ "must": [{ "key": "doc_id", "match": { "value": doc_id } }] });
+    }
+
+    let url = format!("{}/collections/{}/points/search", QDRANT_URL, COLLECTION_NAME);
+    let response = client
+        .post(&url)
+        .json(&body)
+        .send()
+        .await
+        .context("Failed to reach Qdrant")?;
+
+    if !response.status().is_success() {
+        let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+        anyhow::bail!("Qdrant search failed: {}", error_text);
+    }
+
+    let result: serde_json::Value = response.json().await.context("Failed to parse Qdrant response")?;
+    let points = result["result"].as_array().context("Invalid Qdrant response format")?;
+    Ok(points.iter().map(parse_point).collect())
+}
+
+async fn qdrant_scroll(
+    client: &reqwest::Client,
+    doc_id: Option<&str>,
+    limit: usize,
+) -> anyhow::Result<Vec<RawChunk>> {
+    use serde_json::json;
+
+    let mut body = json!({
+        "limit": limit,
+        "with_payload": true,
+    });
+    if let Some(doc_id) = doc_id {
+        body["filter"] = json!({ "must": [{ "key": "doc_id", "match": { "value": doc_id } }] });
+    }
+
+    let url = format!("{}/collections/{}/points/scroll", QDRANT_URL, COLLECTION_NAME);
+    let response = client
+        .post(&url)
+        .json(&body)
+        .send()
+        .await
+        .context("Failed to reach Qdrant")?;
+
+    if !response.status().is_success() {
+        let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+        anyhow::bail!("Qdrant scroll failed: {}", error_text);
+    }
+
+    let result: serde_json::Value = response.json().await.context("Failed to parse Qdrant response")?;
+    let points = result["result"]["points"]
+        .as_array()
+        .context("Invalid Qdrant response format")?;
+    Ok(points.iter().map(parse_point).collect())
+}
+
+fn parse_point(point: &serde_json::Value) -> RawChunk {
+    let payload = point["payload"].as_object();
+    let get = |key: &str| {
+        payload
+            .and_then(|p| p.get(key))
+            .and_then(|v| v.as_str())
+            .unwrap_or("")
+            .to_string()
+    };
+
+    let entity_ids = get("entity_ids")
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .collect();
+
+    RawChunk {
+        chunk_id: get("chunk_id"),
+        doc_id: get("doc_id"),
+        source: get("source"),
+        text: get("text"),
+        entity_ids,
+        score: point.get("score").and_then(|v| v.as_f64()).map(|s| s as f32),
+    }
+}
+
+async fn entity_ids_matching_name(graph: &neo4rs::Graph, name: &str) -> anyhow::Result<HashSet<String>> {
+    let query = Query::new(
+        "MATCH (e:Entity) WHERE toLower(e.name) CONTAINS toLower($name) RETURN e.id as id".to_string(),
+    )
+    .param("name", name.to_string());
+
+    let mut result = graph.execute(query).await?;
+    let mut ids = HashSet::new();
+    while let Some(row) = result.next().await? {
+        if let Ok(id) = row.get::<String>("id") {
+            ids.insert(id);
+        }
+    }
+    Ok(ids)
+}
+
+async fn entity_ids_in_relation(graph: &neo4rs::Graph, relation_type: &str) -> anyhow::Result<HashSet<String>> {
+    let query = Query::new(
+        r#"
+        MATCH (source:Entity)-[r:RELATION {type: $relation_type}]->(target:Entity)
+        RETURN source.id as source_id, target.id as target_id
+        "#
+        .to_string(),
+    )
+    .param("relation_type", relation_type.to_string());
+
+    let mut result = graph.execute(query).await?;
+    let mut ids = HashSet::new();
+    while let Some(row) = result.next().await? {
+        if let Ok(id) = row.get::<String>("source_id") {
+            ids.insert(id);
+        }
+        if let Ok(id) = row.get::<String>("target_id") {
+            ids.insert(id);
+        }
+    }
+    Ok(ids)
+}
+
+async fn entity_details_for(
+    graph: &neo4rs::Graph,
+    ids: &HashSet<String>,
+) -> anyhow::Result<HashMap<String, SearchEntity>> {
+    let id_list: Vec<String> = ids.iter().cloned().collect();
+    let query = Query::new(
+        r#"
+        MATCH (e:Entity)
+        WHERE e.id IN $ids
+        RETURN e.id as id, e.name as name, e.type as type, e.description as description
+        "#
+        .to_string(),
+    )
+    .param("ids", id_list);
+
+    let mut result = graph.execute(query).await?;
+    let mut details = HashMap::new();
+    while let Some(row) = result.next().await? {
+        let id: String = row.get("id")?;
+        details.insert(
+            id.clone(),
+            SearchEntity {
+                id,
+                name: row.get("name").unwrap_or_else(|_| String::new()),
+                entity_type: row.get("type").unwrap_or_else(|_| "UNKNOWN".to_string()),
+                description: row.get("description").unwrap_or_else(|_| String::new()),
+            },
+        );
+    }
+    Ok(details)
+}
+
+async fn relations_among(graph: &neo4rs::Graph, ids: &HashSet<String>) -> anyhow::Result<Vec<SearchRelation>> {
+    let id_list: Vec<String> = ids.iter().cloned().collect();
+    let query = Query::new(
+        r#"
+        MATCH (source:Entity)-[r:RELATION]->(target:Entity)
+        WHERE source.id IN $ids AND target.id IN $ids
+        RETURN source.id as source, r.type as relation, target.id as target, r.evidence as evidence
+        "#
+        .to_string(),
+    )
+    .param("ids", id_list);
+
+    let mut result = graph.execute(query).await?;
+    let mut relations = Vec::new();
+    while let Some(row) = result.next().await? {
+        relations.push(SearchRelation {
+            source: row.get("source")?,
+            relation: row.get("relation")?,
+            target: row.get("target")?,
+            evidence: row.get("evidence").unwrap_or_else(|_| String::new()),
+        });
+    }
+    Ok(relations)
+}
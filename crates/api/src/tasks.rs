@@ -0,0 +1,238 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+use tokio::sync::mpsc;
+
+use crate::error::AppError;
+use crate::scheduler::SchedulerError;
+use crate::{BenchmarkRequest, CommunitiesRequest, ExtractRequest, IngestRequest};
+
+/// What a task was asked to do, carrying whatever the originating request
+/// needs so the worker can run it without touching the HTTP layer again.
+pub enum TaskKind {
+    Ingest(IngestRequest),
+    Extract(Option<ExtractRequest>),
+    Index,
+    Communities(Option<CommunitiesRequest>),
+    Benchmark(Option<BenchmarkRequest>),
+}
+
+impl TaskKind {
+    fn label(&self) -> &'static str {
+        match self {
+            TaskKind::Ingest(_) => "ingest",
+            TaskKind::Extract(_) => "extract",
+            TaskKind::Index => "index",
+            TaskKind::Communities(_) => "communities",
+            TaskKind::Benchmark(_) => "benchmark",
+        }
+    }
+}
+
+/// A unit of work enqueued onto the task worker.
+pub struct Task {
+    pub uid: u64,
+    pub kind: TaskKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskStatus {
+    Enqueued,
+    Processing,
+    Succeeded,
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TaskInfo {
+    pub uid: u64,
+    pub kind: &'static str,
+    pub status: TaskStatus,
+    pub created_at_unix_ms: u128,
+    pub updated_at_unix_ms: u128,
+    pub request: serde_json::Value,
+    pub result: Option<serde_json::Value>,
+    pub error: Option<String>,
+}
+
+fn now_unix_ms() -> u128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis()
+}
+
+/// In-memory record of every task's status, timestamps, and outcome, so
+/// clients can poll `GET /tasks/{uid}` instead of holding an HTTP
+/// connection open for the whole pipeline run.
+#[derive(Clone)]
+pub struct TaskStore {
+    tasks: Arc<Mutex<HashMap<u64, TaskInfo>>>,
+}
+
+impl TaskStore {
+    pub fn new() -> Self {
+        Self {
+            tasks: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    fn insert(&self, info: TaskInfo) {
+        self.tasks.lock().unwrap().insert(info.uid, info);
+    }
+
+    fn update<F: FnOnce(&mut TaskInfo)>(&self, uid: u64, f: F) {
+        if let Some(info) = self.tasks.lock().unwrap().get_mut(&uid) {
+            f(info);
+            info.updated_at_unix_ms = now_unix_ms();
+        }
+    }
+
+    fn set_processing(&self, uid: u64) {
+        self.update(uid, |info| info.status = TaskStatus::Processing);
+    }
+
+    fn set_succeeded(&self, uid: u64, result: serde_json::Value) {
+        self.update(uid, |info| {
+            info.status = TaskStatus::Succeeded;
+            info.result = Some(result);
+        });
+    }
+
+    fn set_failed(&self, uid: u64, error: String) {
+        self.update(uid, |info| {
+            info.status = TaskStatus::Failed;
+            info.error = Some(error);
+        });
+    }
+
+    pub fn get(&self, uid: u64) -> Option<TaskInfo> {
+        self.tasks.lock().unwrap().get(&uid).cloned()
+    }
+
+    pub fn list(&self, status: Option<TaskStatus>) -> Vec<TaskInfo> {
+        let mut tasks: Vec<TaskInfo> = self
+            .tasks
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|info| status.map(|s| s == info.status).unwrap_or(true))
+            .cloned()
+            .collect();
+        tasks.sort_by_key(|info| info.uid);
+        tasks
+    }
+}
+
+/// Handle held by HTTP handlers to enqueue work onto the background task
+/// worker instead of running the pipeline inline.
+#[derive(Clone)]
+pub struct TaskController {
+    sender: mpsc::Sender<Task>,
+    next_uid: Arc<AtomicU64>,
+    pub store: TaskStore,
+}
+
+impl TaskController {
+    pub fn new(sender: mpsc::Sender<Task>, store: TaskStore) -> Self {
+        Self {
+            sender,
+            next_uid: Arc::new(AtomicU64::new(1)),
+            store,
+        }
+    }
+
+    /// Record the task as `Enqueued` and hand it to the worker, returning
+    /// its uid for the caller to poll.
+    pub async fn enqueue(&self, kind: TaskKind, request: serde_json::Value) -> Result<u64, AppError> {
+        let uid = self.next_uid.fetch_add(1, Ordering::SeqCst);
+        let now = now_unix_ms();
+
+        self.store.insert(TaskInfo {
+            uid,
+            kind: kind.label(),
+            status: TaskStatus::Enqueued,
+            created_at_unix_ms: now,
+            updated_at_unix_ms: now,
+            request,
+            result: None,
+            error: None,
+        });
+
+        self.sender
+            .send(Task { uid, kind })
+            .await
+            .map_err(|_| AppError::Internal("task worker is not running".to_string()))?;
+
+        Ok(uid)
+    }
+}
+
+/// Background worker owning the `Extractor`, `Indexer`, and
+/// `CommunityDetector` via `state`. Tasks are received sequentially from
+/// `receiver` but each is spawned onto its own future run through
+/// `state.scheduler`: `Extract` goes through the extraction permit pool,
+/// everything else goes through the LLM permit pool, so several can run
+/// concurrently without
+/// overwhelming either backend - and so a backend that starts erroring or
+/// timing out gets throttled harder automatically.
+pub async fn run_task_worker(mut receiver: mpsc::Receiver<Task>, state: Arc<crate::AppState>) {
+    while let Some(task) = receiver.recv().await {
+        let state = state.clone();
+        tokio::spawn(async move {
+            state.task_store.set_processing(task.uid);
+
+            let outcome = match task.kind {
+                TaskKind::Extract(req) => {
+                    state
+                        .scheduler
+                        .run_extraction(run_extract_task(&state, req))
+                        .await
+                }
+                kind => state.scheduler.run_llm(run_task(&state, kind)).await,
+            };
+
+            match outcome {
+                Ok(value) => state.task_store.set_succeeded(task.uid, value),
+                Err(SchedulerError::Timeout) => {
+                    state.task_store.set_failed(task.uid, "task timed out".to_string())
+                }
+                Err(SchedulerError::Inner(e)) => state.task_store.set_failed(task.uid, e.detail()),
+            }
+        });
+    }
+}
+
+async fn run_extract_task(
+    state: &Arc<crate::AppState>,
+    req: Option<ExtractRequest>,
+) -> Result<serde_json::Value, AppError> {
+    let result = crate::run_extract(state, req).await?;
+    serde_json::to_value(result).map_err(|e| AppError::Internal(e.to_string()))
+}
+
+async fn run_task(state: &Arc<crate::AppState>, kind: TaskKind) -> Result<serde_json::Value, AppError> {
+    match kind {
+        TaskKind::Ingest(req) => {
+            let result = crate::run_ingest(state, req).await?;
+            serde_json::to_value(result).map_err(|e| AppError::Internal(e.to_string()))
+        }
+        TaskKind::Extract(req) => run_extract_task(state, req).await,
+        TaskKind::Index => {
+            let result = crate::run_index(state).await?;
+            serde_json::to_value(result).map_err(|e| AppError::Internal(e.to_string()))
+        }
+        TaskKind::Communities(req) => {
+            let result = crate::run_communities(state, req).await?;
+            serde_json::to_value(result).map_err(|e| AppError::Internal(e.to_string()))
+        }
+        TaskKind::Benchmark(req) => {
+            let result = crate::run_benchmark(state, req).await?;
+            serde_json::to_value(result).map_err(|e| AppError::Internal(e.to_string()))
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+
+/// Fixed-capacity LRU cache keyed by a hash of `(model, content)`. Because
+/// entries are content-addressed there's nothing to invalidate — only
+/// eviction once the cache is at capacity.
+pub struct ContentCache<V: Clone> {
+    capacity: usize,
+    entries: Mutex<HashMap<String, V>>,
+    order: Mutex<VecDeque<String>>,
+}
+
+impl<V: Clone> ContentCache<V> {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: Mutex::new(HashMap::new()),
+            order: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    pub fn get(&self, model: &str, content: &str) -> Option<V> {
+        let key = Self::key_for(model, content);
+        let value = self.entries.lock().unwrap().get(&key).cloned();
+        if value.is_some() {
+            self.touch(&key);
+        }
+        value
+    }
+
+    pub fn put(&self, model: &str, content: &str, value: V) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        let key = Self::key_for(model, content);
+        let mut entries = self.entries.lock().unwrap();
+        let mut order = self.order.lock().unwrap();
+
+        if !entries.contains_key(&key) && entries.len() >= self.capacity {
+            if let Some(oldest) = order.pop_front() {
+                entries.remove(&oldest);
+            }
+        }
+
+        entries.insert(key.clone(), value);
+        order.retain(|k| k != &key);
+        order.push_back(key);
+    }
+
+    fn touch(&self, key: &str) {
+        let mut order = self.order.lock().unwrap();
+        order.retain(|k| k != key);
+        order.push_back(key.to_string());
+    }
+
+    fn key_for(model: &str, content: &str) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(model.as_bytes());
+        hasher.update(b"\0");
+        hasher.update(content.as_bytes());
+        hex::encode(hasher.finalize())
+    }
+}
@@ -0,0 +1,5 @@
+pub mod content_cache;
+pub mod retry;
+
+pub use content_cache::ContentCache;
+pub use retry::{is_transient_error, retry_with_backoff, HttpStatusError, RetryConfig};
@@ -0,0 +1,92 @@
+use std::time::Duration;
+
+use rand::Rng;
+use tokio::time::sleep;
+
+/// Exponential backoff with full jitter for retrying transient failures
+/// against an embedding or LLM backend. Full jitter keeps concurrent
+/// calls that fail together (e.g. a backend restart) from retrying in
+/// lockstep.
+#[derive(Clone)]
+pub struct RetryConfig {
+    pub max_retries: usize,
+    pub initial_backoff_ms: u64,
+    pub max_backoff_ms: u64,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff_ms: 200,
+            max_backoff_ms: 5_000,
+        }
+    }
+}
+
+/// Carries a failed response's status through `anyhow`'s error chain so
+/// `is_transient_error` can tell a rate limit or server failure (worth
+/// retrying) from a client error (never worth retrying) after the status
+/// line has already been folded into a display message.
+#[derive(Debug)]
+pub struct HttpStatusError(pub reqwest::StatusCode);
+
+impl std::fmt::Display for HttpStatusError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for HttpStatusError {}
+
+/// True for a rate limit or server-side failure, and for a transport-level
+/// timeout or connection failure - the cases where the same request is
+/// likely to succeed if retried after a backoff.
+pub fn is_transient_error(e: &anyhow::Error) -> bool {
+    e.chain().any(|cause| {
+        if let Some(status_err) = cause.downcast_ref::<HttpStatusError>() {
+            return status_err.0 == reqwest::StatusCode::TOO_MANY_REQUESTS
+                || status_err.0.is_server_error();
+        }
+        if let Some(req_err) = cause.downcast_ref::<reqwest::Error>() {
+            return req_err.is_timeout() || req_err.is_connect();
+        }
+        false
+    })
+}
+
+/// Retry `op` with full-jitter exponential backoff. `is_retryable`
+/// classifies an error as worth another attempt; anything else is returned
+/// immediately instead of waiting out a backoff that can't help. Stops
+/// after `cfg.max_retries` retries (`max_retries + 1` attempts in all) and
+/// returns the last error.
+pub async fn retry_with_backoff<F, Fut, T>(
+    cfg: &RetryConfig,
+    mut op: F,
+    is_retryable: impl Fn(&anyhow::Error) -> bool,
+) -> anyhow::Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = anyhow::Result<T>>,
+{
+    let mut attempt = 0usize;
+
+    loop {
+        match op().await {
+            Ok(result) => return Ok(result),
+            Err(e) => {
+                if attempt >= cfg.max_retries || !is_retryable(&e) {
+                    return Err(e);
+                }
+
+                let cap_ms = cfg
+                    .initial_backoff_ms
+                    .saturating_mul(1u64 << attempt.min(32))
+                    .min(cfg.max_backoff_ms);
+                let delay_ms = rand::thread_rng().gen_range(0..=cap_ms);
+                sleep(Duration::from_millis(delay_ms)).await;
+                attempt += 1;
+            }
+        }
+    }
+}
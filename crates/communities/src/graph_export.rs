@@ -32,6 +32,16 @@ impl GraphData {
     pub fn add_edge(&mut self, source: usize, target: usize) {
         self.edges.push((source, target));
     }
+
+    /// Partition this graph into communities via Louvain modularity
+    /// maximization, returning clusters as original entity indices.
+    /// `resolution` scales the expected-edges term: above `1.0` favors many
+    /// small communities, below `1.0` favors fewer, larger ones.
+    pub fn communities(&self, resolution: f64) -> Vec<Vec<usize>> {
+        crate::louvain::LouvainDetector::new(self.clone())
+            .with_resolution(resolution)
+            .communities_by_index()
+    }
 }
 
 pub struct GraphExporter {
@@ -77,31 +87,41 @@ impl GraphExporter {
         Ok(graph_data)
     }
 
-    /// Get entity details for a community
+    /// Get entity details for a community in a single round trip, instead of
+    /// one `MATCH` per entity ID.
     pub async fn get_community_entities(
         &self,
         entity_ids: &[String],
     ) -> Result<Vec<EntityInfo>> {
-        let mut entities = Vec::new();
-
-        for entity_id in entity_ids {
-            let query = Query::new(
-                "MATCH (e:Entity {id: $id}) RETURN e.name as name, e.type as type, e.description as description".to_string()
-            ).param("id", entity_id.clone());
-
-            let mut result = self.graph.execute(query).await?;
-            
-            if let Some(row) = result.next().await? {
-                entities.push(EntityInfo {
-                    id: entity_id.clone(),
-                    name: row.get("name").unwrap_or_else(|_| entity_id.clone()),
+        let query = Query::new(
+            r#"
+            MATCH (e:Entity)
+            WHERE e.id IN $entity_ids
+            RETURN e.id as id, e.name as name, e.type as type, e.description as description
+            "#.to_string()
+        ).param("entity_ids", entity_ids.to_vec());
+
+        let mut result = self.graph.execute(query).await?;
+
+        let mut by_id: HashMap<String, EntityInfo> = HashMap::new();
+        while let Some(row) = result.next().await? {
+            let id: String = row.get("id").context("Missing id")?;
+            by_id.insert(
+                id.clone(),
+                EntityInfo {
+                    id: id.clone(),
+                    name: row.get("name").unwrap_or_else(|_| id.clone()),
                     entity_type: row.get("type").unwrap_or_else(|_| "UNKNOWN".to_string()),
                     description: row.get("description").unwrap_or_else(|_| String::new()),
-                });
-            }
+                },
+            );
         }
 
-        Ok(entities)
+        // Preserve the caller's ordering and drop IDs the graph no longer has.
+        Ok(entity_ids
+            .iter()
+            .filter_map(|id| by_id.remove(id))
+            .collect())
     }
 
     /// Get key relationships within a community
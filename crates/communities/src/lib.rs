@@ -41,8 +41,13 @@ impl CommunityDetector {
         }
     }
 
-    /// Full pipeline: detect communities and generate summaries
-    pub async fn detect_and_summarize(&self) -> Result<Vec<CommunitySummary>> {
+    /// Full pipeline: detect the community hierarchy and generate a summary
+    /// for every community at every level, so higher (coarser) levels can
+    /// answer thematic/global queries while lower levels stay specific.
+    /// `resolution` is forwarded to `LouvainDetector::with_resolution`: above
+    /// `1.0` favors many small communities, below `1.0` favors fewer, larger
+    /// ones.
+    pub async fn detect_and_summarize(&self, resolution: f64) -> Result<Vec<CommunitySummary>> {
         // Step 1: Export graph
         println!("Exporting graph from Neo4j...");
         let graph_data = self.exporter.export_graph().await?;
@@ -52,38 +57,44 @@ impl CommunityDetector {
             return Ok(Vec::new());
         }
 
-        // Step 2: Run community detection
-        println!("Running Louvain community detection...");
-        let detector = LouvainDetector::new(graph_data.clone());
-        let communities = detector.detect_communities();
-
-        // Step 3: Assign communities in Neo4j
-        println!("Assigning communities in Neo4j...");
-        self.assign_communities(&communities).await?;
-
-        // Step 4: Group entities by community
-        let mut community_groups: HashMap<usize, Vec<String>> = HashMap::new();
-        for (entity_id, &comm_id) in &communities {
-            community_groups.entry(comm_id)
-                .or_insert_with(Vec::new)
-                .push(entity_id.clone());
+        // Step 2: Run multi-level Louvain community detection
+        println!("Running hierarchical Louvain community detection...");
+        let detector = LouvainDetector::new(graph_data.clone()).with_resolution(resolution);
+        let hierarchy = detector.detect_hierarchy();
+
+        // Step 3: Assign the finest-level communities in Neo4j
+        if let Some(finest) = hierarchy.first() {
+            println!("Assigning communities in Neo4j...");
+            self.assign_communities(finest).await?;
         }
 
-        // Step 5: Generate summaries for each community
+        // Step 4 & 5: For each level, group entities by community and summarize
         println!("Generating community summaries...");
         let mut summaries = Vec::new();
 
-        for (&comm_id, entity_ids) in &community_groups {
-            println!("Processing community {} ({} entities)...", comm_id, entity_ids.len());
-
-            let entities = self.exporter.get_community_entities(entity_ids).await?;
-            let relations = self.exporter.get_community_relations(entity_ids).await?;
-
-            let summary = self.summarizer
-                .summarize_community(comm_id, &entities, &relations)
-                .await?;
-
-            summaries.push(summary);
+        for (level, communities) in hierarchy.iter().enumerate() {
+            let mut community_groups: HashMap<usize, Vec<String>> = HashMap::new();
+            for (entity_id, &comm_id) in communities {
+                community_groups.entry(comm_id)
+                    .or_insert_with(Vec::new)
+                    .push(entity_id.clone());
+            }
+
+            for (&comm_id, entity_ids) in &community_groups {
+                println!(
+                    "Processing level {} community {} ({} entities)...",
+                    level, comm_id, entity_ids.len()
+                );
+
+                let entities = self.exporter.get_community_entities(entity_ids).await?;
+                let relations = self.exporter.get_community_relations(entity_ids).await?;
+
+                let summary = self.summarizer
+                    .summarize_community(comm_id, level, &entities, &relations)
+                    .await?;
+
+                summaries.push(summary);
+            }
         }
 
         Ok(summaries)
@@ -3,81 +3,189 @@ use std::collections::HashMap;
 
 use crate::graph_export::GraphData;
 
+/// Modularity threshold below which another aggregation pass isn't worth it.
+const MIN_MODULARITY_GAIN: f64 = 1e-6;
+const MAX_LOCAL_MOVING_ITERATIONS: usize = 10;
+const MAX_LEVELS: usize = 10;
+
 pub struct LouvainDetector {
     graph: GraphData,
+    resolution: f64,
 }
 
 impl LouvainDetector {
     pub fn new(graph: GraphData) -> Self {
-        Self { graph }
+        Self {
+            graph,
+            resolution: 1.0,
+        }
+    }
+
+    /// Override the resolution parameter (default `1.0`), which scales the
+    /// expected-edges term in the modularity gain: values above `1.0` favor
+    /// many small communities, values below `1.0` favor fewer, larger ones.
+    pub fn with_resolution(mut self, resolution: f64) -> Self {
+        self.resolution = resolution;
+        self
+    }
+
+    /// Run Louvain at the finest level and return clusters as original
+    /// entity indices grouped by community (cluster order is unspecified).
+    pub fn communities_by_index(&self) -> Vec<Vec<usize>> {
+        let assignment = self.detect_communities();
+
+        let mut groups: HashMap<usize, Vec<usize>> = HashMap::new();
+        for (entity_id, community) in assignment {
+            if let Some(&idx) = self.graph.entity_to_idx.get(&entity_id) {
+                groups.entry(community).or_default().push(idx);
+            }
+        }
+
+        let mut clusters: Vec<Vec<usize>> = groups.into_values().collect();
+        for cluster in &mut clusters {
+            cluster.sort_unstable();
+        }
+        clusters
     }
 
-    /// Run Louvain community detection
+    /// Run Louvain community detection (finest level only).
     /// Returns: entity_id -> community_id
     pub fn detect_communities(&self) -> HashMap<String, usize> {
+        self.detect_hierarchy()
+            .into_iter()
+            .next()
+            .unwrap_or_default()
+    }
+
+    /// Run the full multi-level Louvain loop: local moving, then graph
+    /// aggregation (each community becomes a super-node), repeated until
+    /// aggregation no longer improves modularity.
+    ///
+    /// Returns one entity_id -> community_id map per level, finest to coarsest.
+    pub fn detect_hierarchy(&self) -> Vec<HashMap<String, usize>> {
         let n = self.graph.entities.len();
-        
+
         if n == 0 {
-            return HashMap::new();
+            return Vec::new();
         }
 
-        // Initialize: each node in its own community
-        let mut communities: Vec<usize> = (0..n).collect();
-        
-        // Build adjacency list with weights
-        let mut adj_list: Vec<HashMap<usize, f64>> = vec![HashMap::new(); n];
-        let mut total_edges = 0.0;
-        
+        // Build the initial (level 0) weighted adjacency list over entities.
+        let mut adj: Vec<HashMap<usize, f64>> = vec![HashMap::new(); n];
         for &(source, target) in &self.graph.edges {
-            *adj_list[source].entry(target).or_insert(0.0) += 1.0;
-            *adj_list[target].entry(source).or_insert(0.0) += 1.0;
-            total_edges += 2.0; // Undirected
+            *adj[source].entry(target).or_insert(0.0) += 1.0;
+            *adj[target].entry(source).or_insert(0.0) += 1.0;
+        }
+
+        // `members[node]` is the set of original entity indices that this
+        // (possibly aggregated) node represents.
+        let mut members: Vec<Vec<usize>> = (0..n).map(|i| vec![i]).collect();
+
+        let mut hierarchy = Vec::new();
+        let mut prev_modularity = f64::NEG_INFINITY;
+
+        for _ in 0..MAX_LEVELS {
+            let degrees = Self::degrees_from_adj(&adj);
+            let m = degrees.iter().sum::<f64>() / 2.0;
+
+            if m <= 0.0 {
+                break;
+            }
+
+            let communities = Self::local_moving(&adj, &degrees, m, self.resolution);
+            let q = Self::modularity(&adj, &degrees, &communities, m, self.resolution);
+
+            // Stop once another pass no longer improves modularity, or local
+            // moving converged to one node per community (nothing to merge).
+            let (renumbered, level_count) = Self::renumber(&communities);
+            if level_count == adj.len() || q <= prev_modularity + MIN_MODULARITY_GAIN {
+                break;
+            }
+            prev_modularity = q;
+
+            // Translate this level's assignment back to entity IDs.
+            let mut level_map = HashMap::new();
+            for (node, &comm) in renumbered.iter().enumerate() {
+                for &entity_idx in &members[node] {
+                    level_map.insert(self.graph.entities[entity_idx].clone(), comm);
+                }
+            }
+            hierarchy.push(level_map);
+
+            let (new_adj, new_members) = Self::aggregate(&adj, &renumbered, level_count, &members);
+            adj = new_adj;
+            members = new_members;
         }
 
-        // Calculate node degrees
-        let mut degrees: Vec<f64> = vec![0.0; n];
-        for (node, neighbors) in adj_list.iter().enumerate() {
-            degrees[node] = neighbors.values().sum();
+        if hierarchy.is_empty() {
+            // Local moving never merged anything: fall back to "everyone in
+            // their own community" as a single-level result.
+            let mut level_map = HashMap::new();
+            for (idx, entity_id) in self.graph.entities.iter().enumerate() {
+                level_map.insert(entity_id.clone(), idx);
+            }
+            hierarchy.push(level_map);
         }
 
-        let m = total_edges / 2.0; // Total weight of edges
+        println!(
+            "Detected {}-level community hierarchy ({} communities at the finest level)",
+            hierarchy.len(),
+            hierarchy[0].values().collect::<std::collections::HashSet<_>>().len()
+        );
+
+        hierarchy
+    }
+
+    /// Degree of each node: sum of incident edge weights. Self-loops (from
+    /// aggregation) are stored as a single dict entry already holding twice
+    /// the internal weight, so a plain sum is correct.
+    fn degrees_from_adj(adj: &[HashMap<usize, f64>]) -> Vec<f64> {
+        adj.iter().map(|row| row.values().sum()).collect()
+    }
+
+    /// Local-moving phase: repeatedly try moving each node to a neighboring
+    /// community if doing so improves modularity, until no move helps.
+    fn local_moving(adj: &[HashMap<usize, f64>], degrees: &[f64], m: f64, resolution: f64) -> Vec<usize> {
+        let n = adj.len();
+        let mut communities: Vec<usize> = (0..n).collect();
 
-        // Louvain iteration
         let mut improved = true;
         let mut iteration = 0;
-        const MAX_ITERATIONS: usize = 10;
 
-        while improved && iteration < MAX_ITERATIONS {
+        while improved && iteration < MAX_LOCAL_MOVING_ITERATIONS {
             improved = false;
             iteration += 1;
 
-            // Try moving each node to neighboring community
             for node in 0..n {
                 let current_comm = communities[node];
                 let mut best_comm = current_comm;
                 let mut best_gain = 0.0;
 
-                // Get neighboring communities
+                // Get neighboring communities (excluding self-loops, which
+                // are intrinsic to the node and don't affect which
+                // community it should join).
                 let mut neighbor_comms = HashMap::new();
-                for (&neighbor, &weight) in &adj_list[node] {
+                for (&neighbor, &weight) in &adj[node] {
+                    if neighbor == node {
+                        continue;
+                    }
                     let comm = communities[neighbor];
                     *neighbor_comms.entry(comm).or_insert(0.0) += weight;
                 }
 
-                // Try each neighboring community
                 for (&comm, &_weight_to_comm) in &neighbor_comms {
                     if comm == current_comm {
                         continue;
                     }
 
-                    let gain = self.modularity_gain(
+                    let gain = Self::modularity_gain(
                         node,
                         current_comm,
                         comm,
                         &communities,
-                        &degrees,
+                        degrees,
                         &neighbor_comms,
                         m,
+                        resolution,
                     );
 
                     if gain > best_gain {
@@ -86,7 +194,6 @@ impl LouvainDetector {
                     }
                 }
 
-                // Move to best community if improvement found
                 if best_comm != current_comm && best_gain > 0.0 {
                     communities[node] = best_comm;
                     improved = true;
@@ -94,28 +201,11 @@ impl LouvainDetector {
             }
         }
 
-        // Renumber communities to be contiguous (0, 1, 2, ...)
-        let unique_comms: std::collections::HashSet<_> = communities.iter().cloned().collect();
-        let mut comm_mapping: HashMap<usize, usize> = HashMap::new();
-        for (new_id, &old_id) in unique_comms.iter().enumerate() {
-            comm_mapping.insert(old_id, new_id);
-        }
-
-        // Build result map
-        let mut result = HashMap::new();
-        for (idx, entity_id) in self.graph.entities.iter().enumerate() {
-            let old_comm = communities[idx];
-            let new_comm = comm_mapping[&old_comm];
-            result.insert(entity_id.clone(), new_comm);
-        }
-
-        println!("Detected {} communities in {} iterations", unique_comms.len(), iteration);
-        result
+        communities
     }
 
     /// Calculate modularity gain from moving node to a new community
     fn modularity_gain(
-        &self,
         node: usize,
         from_comm: usize,
         to_comm: usize,
@@ -123,6 +213,7 @@ impl LouvainDetector {
         degrees: &[f64],
         neighbor_comms: &HashMap<usize, f64>,
         m: f64,
+        resolution: f64,
     ) -> f64 {
         let k_i = degrees[node];
         let k_i_in_to = neighbor_comms.get(&to_comm).copied().unwrap_or(0.0);
@@ -141,12 +232,86 @@ impl LouvainDetector {
             .map(|(i, _)| degrees[i])
             .sum();
 
-        let delta_q = 
-            (k_i_in_to - k_i_in_from) / (2.0 * m) 
-            - (k_i * (sigma_to - sigma_from + k_i)) / (2.0 * m * m);
+        let delta_q =
+            (k_i_in_to - k_i_in_from) / (2.0 * m)
+            - resolution * (k_i * (sigma_to - sigma_from + k_i)) / (2.0 * m * m);
 
         delta_q
     }
+
+    /// Global modularity `Q = (1/2m) * sum_ij [A_ij - resolution*k_i*k_j/2m] * delta(c_i, c_j)`,
+    /// computed per-community for efficiency.
+    fn modularity(
+        adj: &[HashMap<usize, f64>],
+        degrees: &[f64],
+        communities: &[usize],
+        m: f64,
+        resolution: f64,
+    ) -> f64 {
+        if m <= 0.0 {
+            return 0.0;
+        }
+
+        let mut internal: HashMap<usize, f64> = HashMap::new();
+        let mut total_degree: HashMap<usize, f64> = HashMap::new();
+
+        for node in 0..adj.len() {
+            let c = communities[node];
+            *total_degree.entry(c).or_insert(0.0) += degrees[node];
+
+            for (&neighbor, &weight) in &adj[node] {
+                if communities[neighbor] == c {
+                    *internal.entry(c).or_insert(0.0) += weight;
+                }
+            }
+        }
+
+        let two_m = 2.0 * m;
+        total_degree
+            .iter()
+            .map(|(c, &d)| {
+                internal.get(c).copied().unwrap_or(0.0) / two_m - resolution * (d / two_m).powi(2)
+            })
+            .sum()
+    }
+
+    /// Renumber an arbitrary community-id assignment to be contiguous
+    /// (0, 1, 2, ...). Returns the renumbered assignment and the count of
+    /// distinct communities.
+    fn renumber(communities: &[usize]) -> (Vec<usize>, usize) {
+        let mut id_map: HashMap<usize, usize> = HashMap::new();
+        for &c in communities {
+            let next_id = id_map.len();
+            id_map.entry(c).or_insert(next_id);
+        }
+        let renumbered = communities.iter().map(|c| id_map[c]).collect();
+        (renumbered, id_map.len())
+    }
+
+    /// Build the aggregated graph: each community becomes a super-node,
+    /// inter-community edge weights are summed, and intra-community edges
+    /// become a self-loop capturing the internal weight.
+    fn aggregate(
+        adj: &[HashMap<usize, f64>],
+        communities: &[usize],
+        community_count: usize,
+        members: &[Vec<usize>],
+    ) -> (Vec<HashMap<usize, f64>>, Vec<Vec<usize>>) {
+        let mut new_adj = vec![HashMap::new(); community_count];
+        let mut new_members = vec![Vec::new(); community_count];
+
+        for node in 0..adj.len() {
+            let c = communities[node];
+            new_members[c].extend(members[node].iter().copied());
+
+            for (&neighbor, &weight) in &adj[node] {
+                let cn = communities[neighbor];
+                *new_adj[c].entry(cn).or_insert(0.0) += weight;
+            }
+        }
+
+        (new_adj, new_members)
+    }
 }
 
 #[cfg(test)]
@@ -156,7 +321,7 @@ mod tests {
     #[test]
     fn test_small_graph() {
         let mut graph = GraphData::new();
-        
+
         // Create a simple graph with 2 communities
         let a = graph.add_entity("A".to_string());
         let b = graph.add_entity("B".to_string());
@@ -166,11 +331,11 @@ mod tests {
         // Community 1: A-B
         graph.add_edge(a, b);
         graph.add_edge(b, a);
-        
+
         // Community 2: C-D
         graph.add_edge(c, d);
         graph.add_edge(d, c);
-        
+
         // Weak bridge
         graph.add_edge(b, c);
 
@@ -180,4 +345,30 @@ mod tests {
         println!("Communities: {:?}", communities);
         assert!(communities.len() > 0);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_hierarchy_has_fewer_communities_at_coarser_levels() {
+        let mut graph = GraphData::new();
+        let a = graph.add_entity("A".to_string());
+        let b = graph.add_entity("B".to_string());
+        let c = graph.add_entity("C".to_string());
+        let d = graph.add_entity("D".to_string());
+
+        graph.add_edge(a, b);
+        graph.add_edge(b, a);
+        graph.add_edge(c, d);
+        graph.add_edge(d, c);
+        graph.add_edge(b, c);
+
+        let detector = LouvainDetector::new(graph);
+        let hierarchy = detector.detect_hierarchy();
+
+        assert!(!hierarchy.is_empty());
+        if hierarchy.len() > 1 {
+            let finest_count: std::collections::HashSet<_> = hierarchy[0].values().collect();
+            let coarsest_count: std::collections::HashSet<_> =
+                hierarchy[hierarchy.len() - 1].values().collect();
+            assert!(coarsest_count.len() <= finest_count.len());
+        }
+    }
+}
@@ -1,13 +1,20 @@
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 
+use common::ContentCache;
 use crate::graph_export::{EntityInfo, RelationInfo};
 
+/// Default number of (model, prompt) -> response pairs kept in
+/// `CommunitySummarizer`'s in-memory cache.
+const DEFAULT_RESPONSE_CACHE_CAPACITY: usize = 10_000;
+
 #[derive(Clone)]
 pub struct CommunitySummarizer {
     base_url: String,
     model: String,
     client: reqwest::Client,
+    cache: Arc<ContentCache<String>>,
 }
 
 #[derive(Serialize)]
@@ -25,6 +32,8 @@ struct OllamaResponse {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CommunitySummary {
     pub community_id: usize,
+    /// Louvain hierarchy level this summary was generated at (0 = finest).
+    pub level: usize,
     pub entity_count: usize,
     pub summary: String,
     pub key_entities: Vec<String>,
@@ -36,6 +45,7 @@ impl CommunitySummarizer {
             base_url,
             model,
             client: reqwest::Client::new(),
+            cache: Arc::new(ContentCache::new(DEFAULT_RESPONSE_CACHE_CAPACITY)),
         }
     }
 
@@ -46,10 +56,18 @@ impl CommunitySummarizer {
         )
     }
 
+    /// Override the response cache's capacity (defaults to
+    /// `DEFAULT_RESPONSE_CACHE_CAPACITY`).
+    pub fn with_cache_capacity(mut self, capacity: usize) -> Self {
+        self.cache = Arc::new(ContentCache::new(capacity));
+        self
+    }
+
     /// Generate summary for a community
     pub async fn summarize_community(
         &self,
         community_id: usize,
+        level: usize,
         entities: &[EntityInfo],
         relations: &[RelationInfo],
     ) -> Result<CommunitySummary> {
@@ -66,6 +84,7 @@ impl CommunitySummarizer {
 
         Ok(CommunitySummary {
             community_id,
+            level,
             entity_count: entities.len(),
             summary: summary_text.trim().to_string(),
             key_entities,
@@ -115,7 +134,13 @@ impl CommunitySummarizer {
         prompt
     }
 
+    /// Generate a completion, serving from the content-addressed cache
+    /// when this exact `(model, prompt)` pair has been summarized before.
     async fn generate(&self, prompt: &str) -> Result<String> {
+        if let Some(cached) = self.cache.get(&self.model, prompt) {
+            return Ok(cached);
+        }
+
         let url = format!("{}/api/generate", self.base_url);
 
         let request = OllamaRequest {
@@ -140,6 +165,7 @@ impl CommunitySummarizer {
             .await
             .context("Failed to parse Ollama response")?;
 
+        self.cache.put(&self.model, prompt, ollama_response.response.clone());
         Ok(ollama_response.response)
     }
 }
\ No newline at end of file
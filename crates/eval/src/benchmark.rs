@@ -1,7 +1,10 @@
 use anyhow::Result;
+use futures::stream::FuturesUnordered;
+use futures::{FutureExt, StreamExt};
 use serde::{Deserialize, Serialize};
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
+use crate::load_test::{LatencyHistogram, LoadTestResults, Profiler};
 use crate::test_set::{QAPair, QuestionType, score_answer};
 use crate::vanilla_rag::VanillaRAG;
 
@@ -22,6 +25,11 @@ pub struct MethodResults {
     pub p95_latency_ms: f64,
     pub avg_quality_score: f64,
     pub by_category: Vec<CategoryScore>,
+    /// Raw per-query samples, kept alongside the aggregates so a later
+    /// comparison (see `compare_to_baseline`) can bootstrap confidence
+    /// intervals instead of comparing single point estimates.
+    pub quality_scores: Vec<f64>,
+    pub latencies_ms: Vec<f64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -51,20 +59,20 @@ impl Benchmarker {
         }
     }
 
-    pub async fn run_benchmark(&self, test_set: &[QAPair]) -> Result<BenchmarkResults> {
+    pub async fn run_benchmark(&self, test_set: &[QAPair], top_k: usize) -> Result<BenchmarkResults> {
         println!("Running benchmark with {} questions...", test_set.len());
 
         // Run vanilla RAG
         println!("Testing Vanilla RAG...");
-        let vanilla_results = self.test_vanilla(test_set).await?;
+        let vanilla_results = self.test_vanilla(test_set, top_k).await?;
 
         // Run GraphRAG Local
         println!("Testing GraphRAG Local...");
-        let local_results = self.test_graphrag_local(test_set).await?;
+        let local_results = self.test_graphrag_local(test_set, top_k).await?;
 
-        // Run GraphRAG Global  
+        // Run GraphRAG Global
         println!("Testing GraphRAG Global...");
-        let global_results = self.test_graphrag_global(test_set).await?;
+        let global_results = self.test_graphrag_global(test_set, top_k).await?;
 
         // Calculate comparison
         let comparison = Comparison {
@@ -84,13 +92,105 @@ impl Benchmarker {
         })
     }
 
-    async fn test_vanilla(&self, test_set: &[QAPair]) -> Result<MethodResults> {
+    /// Dispatch Vanilla RAG queries (sampled round-robin from `test_set`) at
+    /// `operations_per_second` for `bench_length_seconds`, recording each
+    /// request's latency into a log-linear histogram rather than a single
+    /// serial sweep. `profiler`, if given, samples resource usage on every
+    /// dispatch tick so a run can correlate CPU/RSS with latency.
+    pub async fn run_load_test(
+        &self,
+        test_set: &[QAPair],
+        operations_per_second: f64,
+        bench_length_seconds: u64,
+        profiler: Option<&dyn Profiler>,
+    ) -> Result<LoadTestResults> {
+        anyhow::ensure!(!test_set.is_empty(), "test set must not be empty");
+        anyhow::ensure!(
+            operations_per_second > 0.0,
+            "operations_per_second must be positive"
+        );
+
+        let period = Duration::from_secs_f64(1.0 / operations_per_second);
+        let test_duration = Duration::from_secs(bench_length_seconds);
+
+        let mut histogram = LatencyHistogram::new();
+        let mut profiler_samples = Vec::new();
+        let mut total_requests: u64 = 0;
+        let mut error_count: u64 = 0;
+        let mut next_question = 0usize;
+
+        let start = Instant::now();
+        let mut next_dispatch = start;
+        let mut in_flight = FuturesUnordered::new();
+
+        while start.elapsed() < test_duration {
+            if Instant::now() >= next_dispatch {
+                let qa = &test_set[next_question % test_set.len()];
+                next_question += 1;
+                in_flight.push(self.timed_vanilla_query(qa.question.clone()));
+                next_dispatch += period;
+
+                if let Some(profiler) = profiler {
+                    profiler_samples.push(profiler.sample(start.elapsed()));
+                }
+            } else {
+                tokio::time::sleep(next_dispatch - Instant::now()).await;
+            }
+
+            while let Some(outcome) = in_flight.next().now_or_never().flatten() {
+                total_requests += 1;
+                match outcome {
+                    Ok(latency_ms) => histogram.record(latency_ms),
+                    Err(_) => error_count += 1,
+                }
+            }
+        }
+
+        // Drain requests still in flight once the dispatch window closes.
+        while let Some(outcome) = in_flight.next().await {
+            total_requests += 1;
+            match outcome {
+                Ok(latency_ms) => histogram.record(latency_ms),
+                Err(_) => error_count += 1,
+            }
+        }
+
+        let elapsed_seconds = start.elapsed().as_secs_f64().max(f64::EPSILON);
+        let error_rate = if total_requests > 0 {
+            error_count as f64 / total_requests as f64
+        } else {
+            0.0
+        };
+
+        Ok(LoadTestResults {
+            target_ops_per_second: operations_per_second,
+            achieved_ops_per_second: total_requests as f64 / elapsed_seconds,
+            duration_seconds: bench_length_seconds,
+            total_requests,
+            error_count,
+            error_rate,
+            p50_latency_ms: histogram.percentile(50.0),
+            p90_latency_ms: histogram.percentile(90.0),
+            p95_latency_ms: histogram.percentile(95.0),
+            p99_latency_ms: histogram.percentile(99.0),
+            profiler_samples,
+        })
+    }
+
+    /// Run a single Vanilla RAG query, returning its latency in milliseconds.
+    async fn timed_vanilla_query(&self, question: String) -> Result<f64> {
+        let start = Instant::now();
+        self.vanilla_rag.search(&question, 5).await?;
+        Ok(start.elapsed().as_secs_f64() * 1000.0)
+    }
+
+    async fn test_vanilla(&self, test_set: &[QAPair], top_k: usize) -> Result<MethodResults> {
         let mut latencies = Vec::new();
         let mut scores = Vec::new();
         let mut category_scores: std::collections::HashMap<String, Vec<f64>> = std::collections::HashMap::new();
 
         for qa in test_set {
-            let result = self.vanilla_rag.search(&qa.question, 5).await?;
+            let result = self.vanilla_rag.search(&qa.question, top_k).await?;
             
             latencies.push(result.query_time_ms as f64);
             let score = score_answer(&result.answer, &qa.expected_answer_contains);
@@ -103,7 +203,7 @@ impl Benchmarker {
         Ok(self.compute_results("Vanilla RAG".to_string(), latencies, scores, category_scores))
     }
 
-    async fn test_graphrag_local(&self, test_set: &[QAPair]) -> Result<MethodResults> {
+    async fn test_graphrag_local(&self, test_set: &[QAPair], top_k: usize) -> Result<MethodResults> {
         let mut latencies = Vec::new();
         let mut scores = Vec::new();
         let mut category_scores: std::collections::HashMap<String, Vec<f64>> = std::collections::HashMap::new();
@@ -112,12 +212,12 @@ impl Benchmarker {
 
         for qa in test_set {
             let start = Instant::now();
-            
+
             let response = client
                 .post(&format!("{}/query/local", self.api_base_url))
                 .json(&serde_json::json!({
                     "query": qa.question,
-                    "top_k": 5
+                    "top_k": top_k
                 }))
                 .send()
                 .await?;
@@ -138,7 +238,7 @@ impl Benchmarker {
         Ok(self.compute_results("GraphRAG Local".to_string(), latencies, scores, category_scores))
     }
 
-    async fn test_graphrag_global(&self, test_set: &[QAPair]) -> Result<MethodResults> {
+    async fn test_graphrag_global(&self, test_set: &[QAPair], top_k: usize) -> Result<MethodResults> {
         let mut latencies = Vec::new();
         let mut scores = Vec::new();
         let mut category_scores: std::collections::HashMap<String, Vec<f64>> = std::collections::HashMap::new();
@@ -152,12 +252,12 @@ impl Benchmarker {
             }
 
             let start = Instant::now();
-            
+
             let response = client
                 .post(&format!("{}/query/global", self.api_base_url))
                 .json(&serde_json::json!({
                     "query": qa.question,
-                    "top_k": 3
+                    "top_k": top_k
                 }))
                 .send()
                 .await?;
@@ -188,8 +288,8 @@ impl Benchmarker {
         latencies.sort_by(|a, b| a.partial_cmp(b).unwrap());
 
         let avg_latency = latencies.iter().sum::<f64>() / latencies.len() as f64;
-        let p50_latency = percentile(&latencies, 50);
-        let p95_latency = percentile(&latencies, 95);
+        let p50_latency = percentile(&latencies, 50.0);
+        let p95_latency = percentile(&latencies, 95.0);
         let avg_quality = scores.iter().sum::<f64>() / scores.len() as f64;
 
         let by_category = category_scores.into_iter()
@@ -208,11 +308,293 @@ impl Benchmarker {
             p95_latency_ms: p95_latency,
             avg_quality_score: avg_quality,
             by_category,
+            quality_scores: scores,
+            latencies_ms: latencies,
+        }
+    }
+
+    /// Serialize `results` to `path` as the baseline future runs are
+    /// compared against.
+    pub fn save_baseline(results: &BenchmarkResults, path: &str) -> Result<()> {
+        let json = serde_json::to_string_pretty(results)?;
+        std::fs::write(path, json)?;
+        Ok(())
+    }
+
+    /// Load a previously saved baseline from `path`.
+    pub fn load_baseline(path: &str) -> Result<BenchmarkResults> {
+        let json = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&json)?)
+    }
+
+    /// Compare `results` against a saved `baseline`, per method, flagging a
+    /// regression only when the new mean falls outside the baseline's
+    /// bootstrap confidence interval (or the two CIs are disjoint) — a plain
+    /// point-estimate delta is too noisy to gate CI on.
+    pub fn compare_to_baseline(
+        results: &BenchmarkResults,
+        baseline: &BenchmarkResults,
+        thresholds: &RegressionThresholds,
+    ) -> ComparisonReport {
+        ComparisonReport {
+            vanilla_rag: compare_method(&results.vanilla_rag, &baseline.vanilla_rag, thresholds),
+            graphrag_local: compare_method(&results.graphrag_local, &baseline.graphrag_local, thresholds),
+            graphrag_global: compare_method(&results.graphrag_global, &baseline.graphrag_global, thresholds),
         }
     }
 }
 
-fn percentile(sorted_data: &[f64], p: usize) -> f64 {
-    let index = (p as f64 / 100.0 * sorted_data.len() as f64) as usize;
-    sorted_data[index.min(sorted_data.len() - 1)]
+/// Thresholds a regression must clear before `compare_to_baseline` flags it,
+/// on top of the bootstrap-CI noise filter.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegressionThresholds {
+    /// Minimum drop in `avg_quality_score` (absolute) to flag as a regression.
+    pub min_quality_drop: f64,
+    /// Minimum increase in p95 latency, as a fraction of the baseline (0.1 = 10%).
+    pub max_latency_increase_ratio: f64,
+}
+
+impl Default for RegressionThresholds {
+    fn default() -> Self {
+        Self {
+            min_quality_drop: 0.02,
+            max_latency_increase_ratio: 0.10,
+        }
+    }
+}
+
+/// Number of bootstrap resamples used to estimate each confidence interval.
+const BOOTSTRAP_ITERATIONS: usize = 1000;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfidenceInterval {
+    pub lower: f64,
+    pub upper: f64,
+}
+
+impl ConfidenceInterval {
+    fn contains(&self, value: f64) -> bool {
+        value >= self.lower && value <= self.upper
+    }
+
+    fn disjoint_from(&self, other: &ConfidenceInterval) -> bool {
+        self.upper < other.lower || other.upper < self.lower
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MethodComparison {
+    pub method: String,
+    pub quality_delta: f64,
+    pub baseline_quality_ci: ConfidenceInterval,
+    pub p95_latency_delta_ms: f64,
+    pub baseline_p95_latency_ci: ConfidenceInterval,
+    pub regressed: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComparisonReport {
+    pub vanilla_rag: MethodComparison,
+    pub graphrag_local: MethodComparison,
+    pub graphrag_global: MethodComparison,
+}
+
+fn compare_method(
+    current: &MethodResults,
+    baseline: &MethodResults,
+    thresholds: &RegressionThresholds,
+) -> MethodComparison {
+    let quality_ci = bootstrap_ci(&baseline.quality_scores, BOOTSTRAP_ITERATIONS, |sample| {
+        sample.iter().sum::<f64>() / sample.len().max(1) as f64
+    });
+    let latency_ci = bootstrap_ci(&baseline.latencies_ms, BOOTSTRAP_ITERATIONS, |sample| {
+        percentile(&sorted(sample), 95.0)
+    });
+
+    let quality_delta = current.avg_quality_score - baseline.avg_quality_score;
+    let p95_latency_delta_ms = current.p95_latency_ms - baseline.p95_latency_ms;
+
+    let current_quality_ci = bootstrap_ci(&current.quality_scores, BOOTSTRAP_ITERATIONS, |sample| {
+        sample.iter().sum::<f64>() / sample.len().max(1) as f64
+    });
+    let current_latency_ci = bootstrap_ci(&current.latencies_ms, BOOTSTRAP_ITERATIONS, |sample| {
+        percentile(&sorted(sample), 95.0)
+    });
+
+    let quality_regressed = -quality_delta >= thresholds.min_quality_drop
+        && (!quality_ci.contains(current.avg_quality_score) || quality_ci.disjoint_from(&current_quality_ci));
+
+    let latency_regressed = p95_latency_delta_ms
+        >= baseline.p95_latency_ms * thresholds.max_latency_increase_ratio
+        && (!latency_ci.contains(current.p95_latency_ms) || latency_ci.disjoint_from(&current_latency_ci));
+
+    MethodComparison {
+        method: current.method.clone(),
+        quality_delta,
+        baseline_quality_ci: quality_ci,
+        p95_latency_delta_ms,
+        baseline_p95_latency_ci: latency_ci,
+        regressed: quality_regressed || latency_regressed,
+    }
+}
+
+fn sorted(data: &[f64]) -> Vec<f64> {
+    let mut data = data.to_vec();
+    data.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    data
+}
+
+/// Bootstrap a 95% confidence interval for `statistic(sample)` by resampling
+/// `data` with replacement `iterations` times and taking the 2.5th/97.5th
+/// percentiles of the resulting distribution.
+fn bootstrap_ci(data: &[f64], iterations: usize, statistic: impl Fn(&[f64]) -> f64) -> ConfidenceInterval {
+    if data.is_empty() {
+        return ConfidenceInterval { lower: 0.0, upper: 0.0 };
+    }
+
+    let mut rng = FastRng::new(0x9E3779B97F4A7C15 ^ data.len() as u64);
+    let mut resample = vec![0.0; data.len()];
+    let mut estimates = Vec::with_capacity(iterations);
+
+    for _ in 0..iterations {
+        for slot in resample.iter_mut() {
+            let idx = (rng.next_u64() as usize) % data.len();
+            *slot = data[idx];
+        }
+        estimates.push(statistic(&resample));
+    }
+
+    estimates.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    ConfidenceInterval {
+        lower: percentile(&estimates, 2.5),
+        upper: percentile(&estimates, 97.5),
+    }
+}
+
+/// Small, fast xorshift64* PRNG. Not cryptographically secure, but bootstrap
+/// resampling only needs a decent-quality, dependency-free source of
+/// randomness that can be seeded deterministically for reproducible runs.
+struct FastRng {
+    state: u64,
+}
+
+impl FastRng {
+    fn new(seed: u64) -> Self {
+        Self { state: seed | 1 }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        self.state.wrapping_mul(0x2545F4914F6CDD1D)
+    }
+}
+
+/// Percentile via linear interpolation between the two nearest ranks
+/// (`sorted_data` must already be sorted ascending).
+fn percentile(sorted_data: &[f64], p: f64) -> f64 {
+    if sorted_data.is_empty() {
+        return 0.0;
+    }
+    if sorted_data.len() == 1 {
+        return sorted_data[0];
+    }
+
+    let rank = (p / 100.0) * (sorted_data.len() - 1) as f64;
+    let lower_idx = rank.floor() as usize;
+    let upper_idx = rank.ceil() as usize;
+
+    if lower_idx == upper_idx {
+        return sorted_data[lower_idx];
+    }
+
+    let frac = rank - lower_idx as f64;
+    sorted_data[lower_idx] * (1.0 - frac) + sorted_data[upper_idx] * frac
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn method_results(method: &str, quality_scores: Vec<f64>, latencies_ms: Vec<f64>) -> MethodResults {
+        let avg_quality = quality_scores.iter().sum::<f64>() / quality_scores.len() as f64;
+        let sorted_latencies = sorted(&latencies_ms);
+        MethodResults {
+            method: method.to_string(),
+            total_queries: latencies_ms.len(),
+            avg_latency_ms: sorted_latencies.iter().sum::<f64>() / sorted_latencies.len() as f64,
+            p50_latency_ms: percentile(&sorted_latencies, 50.0),
+            p95_latency_ms: percentile(&sorted_latencies, 95.0),
+            avg_quality_score: avg_quality,
+            by_category: Vec::new(),
+            quality_scores,
+            latencies_ms,
+        }
+    }
+
+    fn sample_results(quality_scores: Vec<f64>, latencies_ms: Vec<f64>) -> BenchmarkResults {
+        BenchmarkResults {
+            vanilla_rag: method_results("vanilla_rag", quality_scores.clone(), latencies_ms.clone()),
+            graphrag_local: method_results("graphrag_local", quality_scores.clone(), latencies_ms.clone()),
+            graphrag_global: method_results("graphrag_global", quality_scores, latencies_ms),
+            comparison: Comparison {
+                local_vs_vanilla_quality_improvement: 0.0,
+                global_vs_vanilla_quality_improvement: 0.0,
+                local_vs_vanilla_latency_ratio: 1.0,
+            },
+        }
+    }
+
+    #[test]
+    fn save_and_load_baseline_roundtrips() {
+        let path = std::env::temp_dir().join(format!(
+            "graphrag-baseline-test-{}-{}.json",
+            std::process::id(),
+            "roundtrip"
+        ));
+        let path = path.to_str().unwrap();
+
+        let results = sample_results(vec![0.8, 0.9, 0.7], vec![100.0, 120.0, 90.0]);
+        Benchmarker::save_baseline(&results, path).unwrap();
+        let loaded = Benchmarker::load_baseline(path).unwrap();
+
+        assert_eq!(loaded.vanilla_rag.avg_quality_score, results.vanilla_rag.avg_quality_score);
+        assert_eq!(loaded.vanilla_rag.latencies_ms, results.vanilla_rag.latencies_ms);
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn compare_to_baseline_flags_a_clear_quality_regression() {
+        let baseline = sample_results(
+            vec![0.9, 0.92, 0.88, 0.91, 0.89, 0.9, 0.93, 0.87, 0.9, 0.91],
+            vec![100.0; 10],
+        );
+        let regressed = sample_results(
+            vec![0.5, 0.52, 0.48, 0.51, 0.49, 0.5, 0.53, 0.47, 0.5, 0.51],
+            vec![100.0; 10],
+        );
+
+        let report = Benchmarker::compare_to_baseline(&regressed, &baseline, &RegressionThresholds::default());
+
+        assert!(report.vanilla_rag.regressed);
+        assert!(report.vanilla_rag.quality_delta < 0.0);
+    }
+
+    #[test]
+    fn compare_to_baseline_does_not_flag_noise_within_the_baseline_ci() {
+        let baseline = sample_results(
+            vec![0.9, 0.92, 0.88, 0.91, 0.89, 0.9, 0.93, 0.87, 0.9, 0.91],
+            vec![100.0, 105.0, 95.0, 102.0, 98.0, 101.0, 99.0, 103.0, 97.0, 100.0],
+        );
+        // Essentially identical to the baseline - no regression.
+        let current = baseline.clone();
+
+        let report = Benchmarker::compare_to_baseline(&current, &baseline, &RegressionThresholds::default());
+
+        assert!(!report.vanilla_rag.regressed);
+        assert!(!report.graphrag_local.regressed);
+        assert!(!report.graphrag_global.regressed);
+    }
 }
\ No newline at end of file
@@ -1,34 +1,93 @@
 use anyhow::Result;
-use eval::{VanillaRAG, get_test_set, Benchmarker, generate_plots};
+use eval::{
+    DashboardClient, RunMetadata, VanillaRAG, WallClockProfiler, get_test_set, Benchmarker,
+    ComparisonReport, RegressionThresholds, generate_plots,
+};
 use index::EmbeddingClient;
 use query::QueryLLM;
 
-#[tokio::main]
-async fn main() -> Result<()> {
-    println!("=== GraphRAG Benchmark Suite ===\n");
+/// Value of a `--flag value` pair in `args`, if present. Errors if the flag
+/// is given but has no value after it, rather than silently ignoring it.
+fn flag_value<'a>(args: &'a [String], name: &str) -> Result<Option<&'a str>> {
+    match args.iter().position(|a| a == name) {
+        None => Ok(None),
+        Some(i) => match args.get(i + 1) {
+            Some(v) => Ok(Some(v.as_str())),
+            None => anyhow::bail!("{name} requires a path argument"),
+        },
+    }
+}
 
-    // Initialize components
+fn new_benchmarker() -> Benchmarker {
     let embedding_client = EmbeddingClient::default();
     let llm = QueryLLM::default();
 
     let vanilla_rag = VanillaRAG::new(
-        embedding_client,
+        Box::new(embedding_client),
         llm,
         "http://localhost:6333".to_string(),
         "graphrag_chunks".to_string(),
     );
 
-    let benchmarker = Benchmarker::new(
+    Benchmarker::new(
         vanilla_rag,
         "http://localhost:3000".to_string(),
+    )
+}
+
+/// `run_benchmark load-test [operations_per_second] [duration_seconds]`:
+/// dispatch sustained Vanilla RAG traffic instead of the one-shot quality
+/// comparison, for measuring throughput/latency under load. Defaults to 1
+/// op/s for 30s when the args are omitted.
+async fn run_load_test_mode(args: &[String]) -> Result<()> {
+    let operations_per_second: f64 = args.first().map(|s| s.parse()).transpose()?.unwrap_or(1.0);
+    let duration_seconds: u64 = args.get(1).map(|s| s.parse()).transpose()?.unwrap_or(30);
+
+    println!(
+        "=== GraphRAG Load Test: {operations_per_second} ops/s for {duration_seconds}s ===\n"
     );
 
+    let benchmarker = new_benchmarker();
+    let test_set = get_test_set();
+    println!("Test set: {} questions\n", test_set.len());
+
+    let profiler = WallClockProfiler;
+    let results = benchmarker
+        .run_load_test(&test_set, operations_per_second, duration_seconds, Some(&profiler))
+        .await?;
+
+    println!("Total requests: {}", results.total_requests);
+    println!("Achieved: {:.2} ops/s (target {:.2})", results.achieved_ops_per_second, results.target_ops_per_second);
+    println!("Error rate: {:.2}%", results.error_rate * 100.0);
+    println!("P50 latency: {:.0} ms", results.p50_latency_ms);
+    println!("P90 latency: {:.0} ms", results.p90_latency_ms);
+    println!("P95 latency: {:.0} ms", results.p95_latency_ms);
+    println!("P99 latency: {:.0} ms", results.p99_latency_ms);
+
+    let results_json = serde_json::to_string_pretty(&results)?;
+    std::fs::write("load_test_results.json", results_json)?;
+    println!("\n✅ Results saved to load_test_results.json");
+
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().map(String::as_str) == Some("load-test") {
+        return run_load_test_mode(&args[1..]).await;
+    }
+
+    println!("=== GraphRAG Benchmark Suite ===\n");
+
+    let benchmarker = new_benchmarker();
+
     // Get test set
     let test_set = get_test_set();
     println!("Test set: {} questions\n", test_set.len());
 
     // Run benchmark
-    let results = benchmarker.run_benchmark(&test_set).await?;
+    let results = benchmarker.run_benchmark(&test_set, 5).await?;
 
     // Print results
     print_results(&results);
@@ -38,6 +97,30 @@ async fn main() -> Result<()> {
     std::fs::write("benchmark_results.json", results_json)?;
     println!("\n✅ Results saved to benchmark_results.json");
 
+    // `--save-baseline <path>`: persist this run as the baseline future runs
+    // are compared against.
+    if let Some(path) = flag_value(&args, "--save-baseline")? {
+        Benchmarker::save_baseline(&results, path)?;
+        println!("✅ Baseline saved to {path}");
+    }
+
+    // `--compare-baseline <path>`: flag a regression against a previously
+    // saved baseline, failing the run (non-zero exit) if one is found so CI
+    // can gate on it.
+    if let Some(path) = flag_value(&args, "--compare-baseline")? {
+        let baseline = Benchmarker::load_baseline(path)?;
+        let report = Benchmarker::compare_to_baseline(&results, &baseline, &RegressionThresholds::default());
+        print_comparison_report(&report);
+
+        let report_json = serde_json::to_string_pretty(&report)?;
+        std::fs::write("regression_report.json", report_json)?;
+        println!("✅ Regression report saved to regression_report.json");
+
+        if report.vanilla_rag.regressed || report.graphrag_local.regressed || report.graphrag_global.regressed {
+            anyhow::bail!("benchmark regressed against baseline at {path}");
+        }
+    }
+
     // Generate plots
     generate_plots(&results, "plots")?;
     println!("✅ Plots saved to plots/");
@@ -46,9 +129,40 @@ async fn main() -> Result<()> {
     generate_readme_section(&results)?;
     println!("✅ README section saved to BENCHMARK.md");
 
+    // Publish to a remote dashboard for CI-driven trend tracking, if configured.
+    if let Ok(dashboard_url) = std::env::var("DASHBOARD_URL") {
+        let api_key = std::env::var("DASHBOARD_API_KEY").unwrap_or_default();
+        let metadata = RunMetadata {
+            git_sha: current_git_sha(),
+            reason: std::env::var("BENCHMARK_RUN_REASON").unwrap_or_default(),
+            model: std::env::var("OLLAMA_MODEL").unwrap_or_else(|_| "llama3".to_string()),
+            // This binary doesn't query corpus stats today; wire these up
+            // once an API client is available here.
+            corpus_chunks: 0,
+            corpus_entities: 0,
+            corpus_communities: 0,
+        };
+
+        let dashboard = DashboardClient::new(dashboard_url, api_key);
+        dashboard.publish(&results, metadata).await;
+        println!("✅ Published run to dashboard");
+    }
+
     Ok(())
 }
 
+/// Current git SHA, if this binary is running inside a git checkout.
+fn current_git_sha() -> String {
+    std::process::Command::new("git")
+        .args(["rev-parse", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|sha| sha.trim().to_string())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
 fn print_results(results: &eval::BenchmarkResults) {
     println!("\n=== RESULTS ===\n");
 
@@ -75,6 +189,20 @@ fn print_method_results(results: &eval::benchmark::MethodResults) {
     println!("  Avg Quality: {:.2}", results.avg_quality_score);
 }
 
+fn print_comparison_report(report: &ComparisonReport) {
+    println!("\n=== BASELINE COMPARISON ===\n");
+    print_method_comparison("VANILLA RAG", &report.vanilla_rag);
+    print_method_comparison("GRAPHRAG LOCAL", &report.graphrag_local);
+    print_method_comparison("GRAPHRAG GLOBAL", &report.graphrag_global);
+}
+
+fn print_method_comparison(label: &str, comparison: &eval::benchmark::MethodComparison) {
+    let status = if comparison.regressed { "⚠️ REGRESSED" } else { "OK" };
+    println!("📊 {label}: {status}");
+    println!("  Quality delta: {:+.3}", comparison.quality_delta);
+    println!("  P95 latency delta: {:+.0} ms", comparison.p95_latency_delta_ms);
+}
+
 fn generate_readme_section(results: &eval::BenchmarkResults) -> Result<()> {
     let content = format!(
 r#"# Benchmark Results
@@ -0,0 +1,77 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::benchmark::BenchmarkResults;
+
+/// Version of `BenchmarkRunPayload`'s schema, so the dashboard can evolve
+/// without breaking older publishers.
+const PAYLOAD_VERSION: u32 = 1;
+
+/// Metadata tagging a single benchmark run, so a dashboard can track
+/// quality/latency trends across commits rather than just the latest run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunMetadata {
+    pub git_sha: String,
+    pub reason: String,
+    pub model: String,
+    pub corpus_chunks: usize,
+    pub corpus_entities: usize,
+    pub corpus_communities: usize,
+}
+
+/// Versioned payload posted to the dashboard.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchmarkRunPayload {
+    pub version: u32,
+    pub metadata: RunMetadata,
+    pub results: BenchmarkResults,
+}
+
+/// Publishes benchmark runs to a remote dashboard for CI-driven trend
+/// tracking. Failures to reach the dashboard are logged, not propagated,
+/// so a flaky or unreachable dashboard never fails the benchmark run.
+pub struct DashboardClient {
+    dashboard_url: String,
+    api_key: String,
+    client: reqwest::Client,
+}
+
+impl DashboardClient {
+    pub fn new(dashboard_url: String, api_key: String) -> Self {
+        Self {
+            dashboard_url,
+            api_key,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Publish a benchmark run. On any failure (network error, non-2xx
+    /// response), logs a warning and returns without propagating it.
+    pub async fn publish(&self, results: &BenchmarkResults, metadata: RunMetadata) {
+        let payload = BenchmarkRunPayload {
+            version: PAYLOAD_VERSION,
+            metadata,
+            results: results.clone(),
+        };
+
+        if let Err(e) = self.try_publish(&payload).await {
+            eprintln!("Warning: failed to publish benchmark run to dashboard: {}", e);
+        }
+    }
+
+    async fn try_publish(&self, payload: &BenchmarkRunPayload) -> Result<()> {
+        let response = self
+            .client
+            .post(&self.dashboard_url)
+            .bearer_auth(&self.api_key)
+            .json(payload)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            anyhow::bail!("Dashboard responded with {}", response.status());
+        }
+
+        Ok(())
+    }
+}
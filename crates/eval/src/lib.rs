@@ -1,11 +1,18 @@
 pub mod vanilla_rag;
 pub mod test_set;
 pub mod benchmark;
+pub mod dashboard;
+pub mod load_test;
 pub mod plots;
 
 pub use vanilla_rag::VanillaRAG;
 pub use test_set::get_test_set;
-pub use benchmark::{Benchmarker, BenchmarkResults};
+pub use benchmark::{
+    Benchmarker, BenchmarkResults, ComparisonReport, ConfidenceInterval, MethodComparison,
+    RegressionThresholds,
+};
+pub use dashboard::{BenchmarkRunPayload, DashboardClient, RunMetadata};
+pub use load_test::{LoadTestResults, Profiler, SysMonitorProfiler, WallClockProfiler};
 pub use plots::generate_plots;
 
 pub fn add(left: u64, right: u64) -> u64 {
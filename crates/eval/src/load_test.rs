@@ -0,0 +1,154 @@
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Number of log-linear buckets in a `LatencyHistogram`. Bucket `b` covers
+/// `[2^b - 1, 2^(b+1) - 1)` milliseconds, so 64 buckets comfortably spans
+/// sub-millisecond latencies up through multi-hour outliers.
+const BUCKET_COUNT: usize = 64;
+
+/// A log-linear latency histogram. Buckets double in width rather than
+/// storing every raw sample, so a sustained load test can run indefinitely
+/// without its memory footprint growing with request count.
+pub struct LatencyHistogram {
+    counts: Vec<u64>,
+    total: u64,
+}
+
+impl LatencyHistogram {
+    pub fn new() -> Self {
+        Self {
+            counts: vec![0; BUCKET_COUNT],
+            total: 0,
+        }
+    }
+
+    pub fn record(&mut self, latency_ms: f64) {
+        self.counts[Self::bucket_for(latency_ms)] += 1;
+        self.total += 1;
+    }
+
+    fn bucket_for(latency_ms: f64) -> usize {
+        let ms = latency_ms.max(0.0);
+        let bucket = (ms + 1.0).log2().floor() as i64;
+        bucket.clamp(0, BUCKET_COUNT as i64 - 1) as usize
+    }
+
+    /// Approximate upper bound (ms) of the given percentile (0-100), based
+    /// on which bucket it falls into.
+    pub fn percentile(&self, p: f64) -> f64 {
+        if self.total == 0 {
+            return 0.0;
+        }
+
+        let target = ((p / 100.0) * self.total as f64).ceil().max(1.0) as u64;
+        let mut cumulative = 0u64;
+        for (bucket, &count) in self.counts.iter().enumerate() {
+            cumulative += count;
+            if cumulative >= target {
+                return 2f64.powi(bucket as i32 + 1) - 1.0;
+            }
+        }
+        2f64.powi(BUCKET_COUNT as i32) - 1.0
+    }
+}
+
+impl Default for LatencyHistogram {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A single resource-usage sample taken during a load test run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfilerSample {
+    pub elapsed_ms: f64,
+    pub cpu_percent: Option<f64>,
+    pub rss_bytes: Option<u64>,
+}
+
+/// Pluggable resource-usage sampling for a load test, so a run can capture
+/// CPU/RSS alongside latency without `run_load_test` hard-coding how.
+pub trait Profiler: Send + Sync {
+    /// Label used to identify this profiler's samples in output.
+    fn name(&self) -> &str;
+
+    /// Take one sample at `elapsed` time into the run.
+    fn sample(&self, elapsed: Duration) -> ProfilerSample;
+}
+
+/// Baseline profiler that only records elapsed wall-clock time, with no
+/// CPU/RSS sampling. Useful when a run just needs timestamps for later
+/// correlation against latency, or as a dependency-free default.
+pub struct WallClockProfiler;
+
+impl Profiler for WallClockProfiler {
+    fn name(&self) -> &str {
+        "wall_clock"
+    }
+
+    fn sample(&self, elapsed: Duration) -> ProfilerSample {
+        ProfilerSample {
+            elapsed_ms: elapsed.as_secs_f64() * 1000.0,
+            cpu_percent: None,
+            rss_bytes: None,
+        }
+    }
+}
+
+/// Profiler that reads process RSS from `/proc/self/status` (Linux only;
+/// `rss_bytes` is `None` on platforms without it).
+pub struct SysMonitorProfiler;
+
+impl SysMonitorProfiler {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn read_rss_bytes() -> Option<u64> {
+        let status = std::fs::read_to_string("/proc/self/status").ok()?;
+        for line in status.lines() {
+            if let Some(rest) = line.strip_prefix("VmRSS:") {
+                let kb: u64 = rest.trim().trim_end_matches("kB").trim().parse().ok()?;
+                return Some(kb * 1024);
+            }
+        }
+        None
+    }
+}
+
+impl Default for SysMonitorProfiler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Profiler for SysMonitorProfiler {
+    fn name(&self) -> &str {
+        "sys_monitor"
+    }
+
+    fn sample(&self, elapsed: Duration) -> ProfilerSample {
+        ProfilerSample {
+            elapsed_ms: elapsed.as_secs_f64() * 1000.0,
+            cpu_percent: None,
+            rss_bytes: Self::read_rss_bytes(),
+        }
+    }
+}
+
+/// Results of a sustained-throughput load test, as opposed to the single
+/// serial sweep `run_benchmark` performs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoadTestResults {
+    pub target_ops_per_second: f64,
+    pub achieved_ops_per_second: f64,
+    pub duration_seconds: u64,
+    pub total_requests: u64,
+    pub error_count: u64,
+    pub error_rate: f64,
+    pub p50_latency_ms: f64,
+    pub p90_latency_ms: f64,
+    pub p95_latency_ms: f64,
+    pub p99_latency_ms: f64,
+    pub profiler_samples: Vec<ProfilerSample>,
+}
@@ -1,12 +1,21 @@
 use anyhow::Result;
+use futures::Stream;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
-use index::EmbeddingClient;
+use index::Embedder;
 use query::QueryLLM;
 
+/// BM25 tuning constants, standard Okapi defaults.
+const BM25_K1: f32 = 1.2;
+const BM25_B: f32 = 0.75;
+
+/// Reciprocal Rank Fusion constant (Cormack et al.).
+const RRF_K: f32 = 60.0;
+
 /// Vanilla RAG: just vector search + LLM, no graph
 pub struct VanillaRAG {
-    embedding_client: EmbeddingClient,
+    embedder: Box<dyn Embedder>,
     llm: QueryLLM,
     qdrant_url: String,
     collection_name: String,
@@ -26,15 +35,37 @@ pub struct Source {
     pub score: f32,
 }
 
+/// How `VanillaRAG::search` should retrieve candidate chunks.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "lowercase")]
+pub enum SearchMode {
+    /// Dense vector search only (the original behavior).
+    Dense,
+    /// BM25 over tokenized chunk text only.
+    Lexical,
+    /// Combine dense and lexical rankings.
+    ///
+    /// `alpha: None` fuses the two ranked lists with Reciprocal Rank
+    /// Fusion. `alpha: Some(a)` instead linearly blends min-max
+    /// normalized scores as `a * dense + (1 - a) * lexical`.
+    Hybrid { alpha: Option<f32> },
+}
+
+impl Default for SearchMode {
+    fn default() -> Self {
+        SearchMode::Dense
+    }
+}
+
 impl VanillaRAG {
     pub fn new(
-        embedding_client: EmbeddingClient,
+        embedder: Box<dyn Embedder>,
         llm: QueryLLM,
         qdrant_url: String,
         collection_name: String,
     ) -> Self {
         Self {
-            embedding_client,
+            embedder,
             llm,
             qdrant_url,
             collection_name,
@@ -42,13 +73,37 @@ impl VanillaRAG {
     }
 
     pub async fn search(&self, query: &str, top_k: usize) -> Result<VanillaSearchResult> {
+        self.search_with_mode(query, top_k, SearchMode::Dense).await
+    }
+
+    pub async fn search_with_mode(
+        &self,
+        query: &str,
+        top_k: usize,
+        mode: SearchMode,
+    ) -> Result<VanillaSearchResult> {
         let start = std::time::Instant::now();
 
-        // Step 1: Embed query
-        let query_embedding = self.embedding_client.embed(query).await?;
+        // Step 1 & 2: retrieve candidates according to the requested mode
+        let sources = match mode {
+            SearchMode::Dense => {
+                let query_embedding = self.embedder.embed(query).await?;
+                self.vector_search(query_embedding, top_k).await?
+            }
+            SearchMode::Lexical => self.lexical_search(query, top_k).await?,
+            SearchMode::Hybrid { alpha } => {
+                let query_embedding = self.embedder.embed(query).await?;
+                // Over-fetch both lists so fusion has enough candidates to work with.
+                let fetch_k = top_k * 4;
+                let dense = self.vector_search(query_embedding, fetch_k).await?;
+                let lexical = self.lexical_search(query, fetch_k).await?;
 
-        // Step 2: Vector search
-        let sources = self.vector_search(query_embedding, top_k).await?;
+                match alpha {
+                    Some(alpha) => fuse_linear(&dense, &lexical, alpha, top_k),
+                    None => fuse_rrf(&dense, &lexical, top_k),
+                }
+            }
+        };
 
         // Step 3: Build simple context (just chunks, no graph)
         let context = self.build_context(&sources);
@@ -63,6 +118,100 @@ impl VanillaRAG {
         })
     }
 
+    /// Like `search`, but resolves `sources` up front and streams the answer
+    /// token-by-token so callers can render partial output as it arrives.
+    pub async fn search_stream(
+        &self,
+        query: &str,
+        top_k: usize,
+    ) -> Result<(Vec<Source>, impl Stream<Item = Result<String>>)> {
+        let query_embedding = self.embedder.embed(query).await?;
+        let sources = self.vector_search(query_embedding, top_k).await?;
+        let context = self.build_context(&sources);
+        let prompt = build_answer_prompt(query, &context);
+
+        Ok((sources, self.llm.generate_stream(&prompt)))
+    }
+
+    /// Rank candidate chunks with BM25 over their tokenized text.
+    async fn lexical_search(&self, query: &str, top_k: usize) -> Result<Vec<Source>> {
+        let corpus = self.fetch_all_chunks().await?;
+        if corpus.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let query_terms = tokenize(query);
+        let scores = bm25_scores(&query_terms, &corpus);
+
+        let mut ranked: Vec<(usize, f32)> = scores.into_iter().enumerate().collect();
+        ranked.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+        Ok(ranked
+            .into_iter()
+            .take(top_k)
+            .map(|(i, score)| Source {
+                chunk_id: corpus[i].chunk_id.clone(),
+                text: corpus[i].text.clone(),
+                score,
+            })
+            .collect())
+    }
+
+    /// Page through the whole collection via Qdrant's scroll API to build the
+    /// BM25 candidate corpus (document frequencies and average length are
+    /// computed over this set).
+    async fn fetch_all_chunks(&self) -> Result<Vec<Source>> {
+        use serde_json::json;
+
+        let client = reqwest::Client::new();
+        let url = format!("{}/collections/{}/points/scroll", self.qdrant_url, self.collection_name);
+
+        let mut chunks = Vec::new();
+        let mut offset: Option<serde_json::Value> = None;
+
+        loop {
+            let mut body = json!({
+                "limit": 256,
+                "with_payload": true,
+                "with_vector": false,
+            });
+            if let Some(offset) = &offset {
+                body["offset"] = offset.clone();
+            }
+
+            let response = client.post(&url).json(&body).send().await?;
+            let result: serde_json::Value = response.json().await?;
+
+            let points = result["result"]["points"].as_array().cloned().unwrap_or_default();
+            if points.is_empty() {
+                break;
+            }
+
+            for point in &points {
+                let payload = point["payload"].as_object();
+                let chunk_id = payload
+                    .and_then(|p| p.get("chunk_id"))
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("")
+                    .to_string();
+                let text = payload
+                    .and_then(|p| p.get("text"))
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("")
+                    .to_string();
+                chunks.push(Source { chunk_id, text, score: 0.0 });
+            }
+
+            let next = &result["result"]["next_page_offset"];
+            if next.is_null() {
+                break;
+            }
+            offset = Some(next.clone());
+        }
+
+        Ok(chunks)
+    }
+
     async fn vector_search(&self, embedding: Vec<f32>, top_k: usize) -> Result<Vec<Source>> {
         use serde_json::json;
 
@@ -70,7 +219,7 @@ impl VanillaRAG {
         let url = format!("{}/collections/{}/points/search", self.qdrant_url, self.collection_name);
 
         let body = json!({
-            "vector": embedding,
+            "vector": { "name": "dense", "vector": embedding },
             "limit": top_k,
             "with_payload": true
         });
@@ -113,8 +262,13 @@ impl VanillaRAG {
     }
 
     async fn generate_answer(&self, query: &str, context: &str) -> Result<String> {
-        let prompt = format!(
-            r#"Answer the question based on the provided context.
+        self.llm.generate(&build_answer_prompt(query, context)).await
+    }
+}
+
+fn build_answer_prompt(query: &str, context: &str) -> String {
+    format!(
+        r#"Answer the question based on the provided context.
 
 CONTEXT:
 {}
@@ -122,9 +276,165 @@ CONTEXT:
 QUESTION: {}
 
 ANSWER:"#,
-            context, query
-        );
+        context, query
+    )
+}
+
+fn tokenize(text: &str) -> Vec<String> {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .map(|t| t.to_string())
+        .collect()
+}
+
+/// Score every document in `corpus` against `query_terms` with Okapi BM25.
+fn bm25_scores(query_terms: &[String], corpus: &[Source]) -> Vec<f32> {
+    let docs: Vec<Vec<String>> = corpus.iter().map(|s| tokenize(&s.text)).collect();
+    let n = docs.len() as f32;
+    let avgdl = docs.iter().map(|d| d.len()).sum::<usize>() as f32 / n;
+
+    let mut doc_freq: HashMap<&str, usize> = HashMap::new();
+    for doc in &docs {
+        for term in query_terms {
+            if doc.iter().any(|t| t == term) {
+                *doc_freq.entry(term.as_str()).or_insert(0) += 1;
+            }
+        }
+    }
+
+    docs.iter()
+        .map(|doc| {
+            let dl = doc.len() as f32;
+            let mut term_freq: HashMap<&str, usize> = HashMap::new();
+            for t in doc {
+                *term_freq.entry(t.as_str()).or_insert(0) += 1;
+            }
+
+            query_terms
+                .iter()
+                .map(|term| {
+                    let n_t = *doc_freq.get(term.as_str()).unwrap_or(&0) as f32;
+                    let idf = ((n - n_t + 0.5) / (n_t + 0.5) + 1.0).ln();
+                    let f = *term_freq.get(term.as_str()).unwrap_or(&0) as f32;
+                    idf * (f * (BM25_K1 + 1.0)) / (f + BM25_K1 * (1.0 - BM25_B + BM25_B * dl / avgdl))
+                })
+                .sum()
+        })
+        .collect()
+}
+
+/// Fuse two ranked lists with Reciprocal Rank Fusion: `RRF(d) = sum_r 1/(k + rank_r(d))`.
+/// A chunk absent from a list simply contributes 0 for that list.
+fn fuse_rrf(dense: &[Source], lexical: &[Source], top_k: usize) -> Vec<Source> {
+    let mut rrf_scores: HashMap<&str, f32> = HashMap::new();
+    let mut by_id: HashMap<&str, &Source> = HashMap::new();
+
+    for (rank, source) in dense.iter().enumerate() {
+        *rrf_scores.entry(&source.chunk_id).or_insert(0.0) += 1.0 / (RRF_K + rank as f32 + 1.0);
+        by_id.entry(&source.chunk_id).or_insert(source);
+    }
+    for (rank, source) in lexical.iter().enumerate() {
+        *rrf_scores.entry(&source.chunk_id).or_insert(0.0) += 1.0 / (RRF_K + rank as f32 + 1.0);
+        by_id.entry(&source.chunk_id).or_insert(source);
+    }
+
+    let mut fused: Vec<(&str, f32)> = rrf_scores.into_iter().collect();
+    fused.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+    fused
+        .into_iter()
+        .take(top_k)
+        .map(|(chunk_id, score)| {
+            let source = by_id[chunk_id];
+            Source {
+                chunk_id: source.chunk_id.clone(),
+                text: source.text.clone(),
+                score,
+            }
+        })
+        .collect()
+}
+
+/// Fuse two ranked lists by linearly blending their min-max normalized scores:
+/// `alpha * dense_norm + (1 - alpha) * lexical_norm`.
+fn fuse_linear(dense: &[Source], lexical: &[Source], alpha: f32, top_k: usize) -> Vec<Source> {
+    let dense_norm = min_max_normalize(dense);
+    let lexical_norm = min_max_normalize(lexical);
+
+    let mut blended: HashMap<&str, f32> = HashMap::new();
+    let mut by_id: HashMap<&str, &Source> = HashMap::new();
+
+    for (source, score) in dense.iter().zip(dense_norm) {
+        *blended.entry(&source.chunk_id).or_insert(0.0) += alpha * score;
+        by_id.entry(&source.chunk_id).or_insert(source);
+    }
+    for (source, score) in lexical.iter().zip(lexical_norm) {
+        *blended.entry(&source.chunk_id).or_insert(0.0) += (1.0 - alpha) * score;
+        by_id.entry(&source.chunk_id).or_insert(source);
+    }
+
+    let mut fused: Vec<(&str, f32)> = blended.into_iter().collect();
+    fused.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+    fused
+        .into_iter()
+        .take(top_k)
+        .map(|(chunk_id, score)| {
+            let source = by_id[chunk_id];
+            Source {
+                chunk_id: source.chunk_id.clone(),
+                text: source.text.clone(),
+                score,
+            }
+        })
+        .collect()
+}
+
+fn min_max_normalize(sources: &[Source]) -> Vec<f32> {
+    if sources.is_empty() {
+        return Vec::new();
+    }
+    let min = sources.iter().map(|s| s.score).fold(f32::INFINITY, f32::min);
+    let max = sources.iter().map(|s| s.score).fold(f32::NEG_INFINITY, f32::max);
+    let range = max - min;
+
+    sources
+        .iter()
+        .map(|s| if range > 0.0 { (s.score - min) / range } else { 0.0 })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rrf_prefers_documents_ranked_highly_in_both_lists() {
+        let dense = vec![
+            Source { chunk_id: "a".into(), text: "x".into(), score: 0.9 },
+            Source { chunk_id: "b".into(), text: "y".into(), score: 0.5 },
+        ];
+        let lexical = vec![
+            Source { chunk_id: "b".into(), text: "y".into(), score: 3.0 },
+            Source { chunk_id: "a".into(), text: "x".into(), score: 1.0 },
+        ];
+
+        let fused = fuse_rrf(&dense, &lexical, 2);
+        assert_eq!(fused.len(), 2);
+        // "a" is first in dense and second in lexical, "b" is second in dense
+        // and first in lexical, so the RRF scores tie and both should appear.
+        assert!(fused.iter().any(|s| s.chunk_id == "a"));
+        assert!(fused.iter().any(|s| s.chunk_id == "b"));
+    }
 
-        self.llm.generate(&prompt).await
+    #[test]
+    fn bm25_scores_rank_term_matches_above_non_matches() {
+        let corpus = vec![
+            Source { chunk_id: "1".into(), text: "the quick brown fox".into(), score: 0.0 },
+            Source { chunk_id: "2".into(), text: "totally unrelated text".into(), score: 0.0 },
+        ];
+        let scores = bm25_scores(&tokenize("quick fox"), &corpus);
+        assert!(scores[0] > scores[1]);
     }
 }
\ No newline at end of file
@@ -1,4 +1,6 @@
 use anyhow::{Context, Result};
+use async_stream::stream;
+use futures::Stream;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone)]
@@ -13,7 +15,8 @@ struct OllamaRequest {
     model: String,
     prompt: String,
     stream: bool,
-    format: String, // "json" for structured output
+    #[serde(skip_serializing_if = "Option::is_none")]
+    format: Option<String>, // Some("json") for structured output, None for free-text
 }
 
 #[derive(Deserialize)]
@@ -21,6 +24,14 @@ struct OllamaResponse {
     response: String,
 }
 
+/// A single line of Ollama's newline-delimited `stream: true` response.
+#[derive(Deserialize)]
+struct OllamaStreamChunk {
+    response: String,
+    #[serde(default)]
+    done: bool,
+}
+
 impl OllamaClient {
     pub fn new(base_url: String, model: String) -> Self {
         Self {
@@ -37,14 +48,17 @@ impl OllamaClient {
         )
     }
 
-    pub async fn generate(&self, prompt: &str) -> Result<String> {
+    /// Generate a complete response. `json_mode` forces Ollama's structured
+    /// `format: "json"` output; pass `false` for free-text prose (e.g.
+    /// answer generation, as opposed to entity/relation extraction).
+    pub async fn generate(&self, prompt: &str, json_mode: bool) -> Result<String> {
         let url = format!("{}/api/generate", self.base_url);
-        
+
         let request = OllamaRequest {
             model: self.model.clone(),
             prompt: prompt.to_string(),
             stream: false,
-            format: "json".to_string(), // Force JSON output
+            format: json_mode.then(|| "json".to_string()),
         };
 
         let response = self.client
@@ -66,6 +80,58 @@ impl OllamaClient {
         Ok(ollama_response.response)
     }
 
+    /// Stream a free-text response token-by-token by consuming Ollama's
+    /// newline-delimited `stream: true` response.
+    pub fn generate_stream(&self, prompt: &str) -> impl Stream<Item = Result<String>> {
+        let client = self.client.clone();
+        let url = format!("{}/api/generate", self.base_url);
+        let request = OllamaRequest {
+            model: self.model.clone(),
+            prompt: prompt.to_string(),
+            stream: true,
+            format: None,
+        };
+
+        stream! {
+            let response = client
+                .post(&url)
+                .json(&request)
+                .send()
+                .await
+                .context("Failed to send streaming request to Ollama")?;
+
+            if !response.status().is_success() {
+                anyhow::bail!("Ollama streaming request failed: {}", response.status());
+            }
+
+            let mut response = response;
+            let mut buffer = String::new();
+
+            while let Some(bytes) = response.chunk().await.context("Failed to read stream chunk")? {
+                buffer.push_str(&String::from_utf8_lossy(&bytes));
+
+                while let Some(newline_pos) = buffer.find('\n') {
+                    let line = buffer[..newline_pos].trim().to_string();
+                    buffer.drain(..=newline_pos);
+
+                    if line.is_empty() {
+                        continue;
+                    }
+
+                    let chunk: OllamaStreamChunk = serde_json::from_str(&line)
+                        .context("Failed to parse Ollama stream chunk")?;
+
+                    if !chunk.response.is_empty() {
+                        yield Ok(chunk.response);
+                    }
+                    if chunk.done {
+                        return;
+                    }
+                }
+            }
+        }
+    }
+
     /// Generate with retry for invalid JSON
     pub async fn generate_json_with_retry(
         &self,
@@ -73,21 +139,21 @@ impl OllamaClient {
         max_retries: usize,
     ) -> Result<String> {
         for attempt in 0..max_retries {
-            let response = self.generate(prompt).await?;
-            
+            let response = self.generate(prompt, true).await?;
+
             // Try to parse as JSON
             if serde_json::from_str::<serde_json::Value>(&response).is_ok() {
                 return Ok(response);
             }
-            
+
             // If invalid, retry with correction prompt
             if attempt < max_retries - 1 {
                 let retry_prompt = format!(
                     "The following JSON is invalid:\n{}\n\nFix this JSON. Output only valid JSON.",
                     response
                 );
-                
-                let corrected = self.generate(&retry_prompt).await?;
+
+                let corrected = self.generate(&retry_prompt, true).await?;
                 if serde_json::from_str::<serde_json::Value>(&corrected).is_ok() {
                     return Ok(corrected);
                 }
@@ -1,15 +1,47 @@
 use regex::Regex;
 use std::collections::HashMap;
 
+/// Tunable knobs for [`EntityNormalizer`]'s fuzzy-merge behavior.
+#[derive(Debug, Clone)]
+pub struct NormalizerConfig {
+    /// Minimum similarity score (see `are_similar`) required to merge two
+    /// normalized names into the same canonical entity.
+    pub similarity_threshold: f64,
+    /// A pure substring match (e.g. "ai" ⊂ "brain") is only accepted as a
+    /// merge when the shorter string is at least this many characters long,
+    /// which keeps short acronyms and common words from over-merging.
+    pub min_length_for_substring_merge: usize,
+}
+
+impl Default for NormalizerConfig {
+    fn default() -> Self {
+        Self {
+            similarity_threshold: 0.88,
+            min_length_for_substring_merge: 4,
+        }
+    }
+}
+
 pub struct EntityNormalizer {
     /// Maps normalized name -> canonical name
     aliases: HashMap<String, String>,
+    /// Blocking index: block signature -> normalized names sharing it, so
+    /// `normalize` only runs fuzzy comparisons against plausible candidates
+    /// instead of scanning every alias ever seen.
+    blocks: HashMap<String, Vec<String>>,
+    config: NormalizerConfig,
 }
 
 impl EntityNormalizer {
     pub fn new() -> Self {
+        Self::with_config(NormalizerConfig::default())
+    }
+
+    pub fn with_config(config: NormalizerConfig) -> Self {
         Self {
             aliases: HashMap::new(),
+            blocks: HashMap::new(),
+            config,
         }
     }
 
@@ -17,69 +49,98 @@ impl EntityNormalizer {
     pub fn normalize(&mut self, name: &str) -> String {
         // Convert to lowercase
         let mut normalized = name.to_lowercase();
-        
+
         // Trim leading/trailing punctuation and whitespace
         normalized = normalized.trim().to_string();
-        
+
         // Remove common punctuation
         let re = Regex::new(r"[.,!?;:']").unwrap();
         normalized = re.replace_all(&normalized, "").to_string();
-        
+
         // Collapse multiple spaces
         let re = Regex::new(r"\s+").unwrap();
         normalized = re.replace_all(&normalized, " ").to_string();
-        
+
         // Check if we've seen a similar entity
         if let Some(canonical) = self.aliases.get(&normalized) {
             return canonical.clone();
         }
-        
-        // Check for near-duplicates (simple fuzzy matching)
+
+        // Check for near-duplicates, restricted to the same block so this
+        // stays sub-linear in the number of aliases seen so far.
+        let block_key = Self::block_key(&normalized);
         let mut found_canonical = None;
 
-        for (existing_norm, canonical) in &self.aliases {
-            if self.are_similar(&normalized, existing_norm) {
-                // Map this new variant to the existing canonical form
-                found_canonical = Some(canonical.clone());
-                break;
+        if let Some(candidates) = self.blocks.get(&block_key) {
+            for existing_norm in candidates {
+                if self.are_similar(&normalized, existing_norm) {
+                    found_canonical = self.aliases.get(existing_norm).cloned();
+                    break;
+                }
             }
         }
-        
-        if let Some(canonical) = found_canonical {
-            self.aliases.insert(normalized.clone(), canonical.clone());
-            return canonical.clone();
-        }
-        
-        // This is a new entity - use the normalized form as canonical
-        self.aliases.insert(normalized.clone(), normalized.clone());
+
+        let canonical = found_canonical.unwrap_or_else(|| normalized.clone());
+
+        self.aliases.insert(normalized.clone(), canonical.clone());
+        self.blocks.entry(block_key).or_default().push(normalized);
+
+        canonical
+    }
+
+    /// Blocking signature: the first two characters of the name's first
+    /// word. Variants that add or drop trailing words ("openai" / "openai
+    /// inc") or differ by a typo past the second character share a block,
+    /// so only candidates in the same block need a full similarity
+    /// comparison; names whose first two characters differ are never
+    /// compared.
+    fn block_key(normalized: &str) -> String {
         normalized
+            .split_whitespace()
+            .next()
+            .map(|w| w.chars().take(2).collect())
+            .unwrap_or_default()
     }
 
-    /// Simple similarity check - can be improved with edit distance
+    /// Similarity score combining normalized Levenshtein distance and
+    /// Jaro-Winkler, using word-overlap Jaccard as a tiebreaker. Pure
+    /// substring matches are only trusted when the shorter string clears
+    /// `min_length_for_substring_merge`, which is what keeps "AI" from
+    /// merging into "brain".
     fn are_similar(&self, a: &str, b: &str) -> bool {
-        // Same after normalization
         if a == b {
             return true;
         }
-        
-        // One is contained in the other (handles AI vs artificial intelligence)
-        if a.contains(b) || b.contains(a) {
+
+        if (a.contains(b) || b.contains(a))
+            && a.len().min(b.len()) >= self.config.min_length_for_substring_merge
+        {
+            return true;
+        }
+
+        let lev_sim = 1.0 - levenshtein(a, b) as f64 / a.len().max(b.len()).max(1) as f64;
+        let jw_sim = jaro_winkler(a, b);
+        let score = lev_sim.max(jw_sim);
+
+        if score >= self.config.similarity_threshold {
             return true;
         }
-        
-        // Check if they share most words (for multi-word entities)
+
+        // Word-overlap Jaccard as a tiebreaker for multi-word entities whose
+        // edit distance is large (word reordering, inserted middle name)
+        // but that still clearly refer to the same thing.
         let words_a: Vec<&str> = a.split_whitespace().collect();
         let words_b: Vec<&str> = b.split_whitespace().collect();
-        
+
         if words_a.len() > 1 && words_b.len() > 1 {
             let common: usize = words_a.iter()
                 .filter(|w| words_b.contains(w))
                 .count();
-            
+
             let total = words_a.len().max(words_b.len());
             return common as f64 / total as f64 > 0.7; // 70% overlap
         }
-        
+
         false
     }
 
@@ -89,6 +150,110 @@ impl EntityNormalizer {
     }
 }
 
+/// Standard two-row Levenshtein edit distance, O(|a|*|b|) time and
+/// O(min(|a|,|b|)) space.
+fn levenshtein(a: &str, b: &str) -> usize {
+    let (a, b): (Vec<char>, Vec<char>) = if a.chars().count() <= b.chars().count() {
+        (a.chars().collect(), b.chars().collect())
+    } else {
+        (b.chars().collect(), a.chars().collect())
+    };
+
+    let mut prev: Vec<usize> = (0..=a.len()).collect();
+    let mut curr = vec![0usize; a.len() + 1];
+
+    for (j, &cb) in b.iter().enumerate() {
+        curr[0] = j + 1;
+        for (i, &ca) in a.iter().enumerate() {
+            let cost = if ca == cb { 0 } else { 1 };
+            curr[i + 1] = (prev[i + 1] + 1)
+                .min(curr[i] + 1)
+                .min(prev[i] + cost);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+
+    prev[a.len()]
+}
+
+/// Jaro-Winkler similarity: Jaro similarity from the matching-window +
+/// transposition count, plus a 0.1-weighted boost for a shared prefix of
+/// up to 4 characters.
+fn jaro_winkler(a: &str, b: &str) -> f64 {
+    let jaro = jaro_similarity(a, b);
+    if jaro <= 0.0 {
+        return jaro;
+    }
+
+    let a_chars: Vec<char> = a.chars().collect();
+    let b_chars: Vec<char> = b.chars().collect();
+    let prefix_len = a_chars
+        .iter()
+        .zip(b_chars.iter())
+        .take(4)
+        .take_while(|(ca, cb)| ca == cb)
+        .count();
+
+    jaro + prefix_len as f64 * 0.1 * (1.0 - jaro)
+}
+
+fn jaro_similarity(a: &str, b: &str) -> f64 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    if a.is_empty() && b.is_empty() {
+        return 1.0;
+    }
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+
+    let match_distance = (a.len().max(b.len()) / 2).saturating_sub(1);
+
+    let mut a_matches = vec![false; a.len()];
+    let mut b_matches = vec![false; b.len()];
+    let mut matches = 0usize;
+
+    for i in 0..a.len() {
+        let lo = i.saturating_sub(match_distance);
+        let hi = (i + match_distance + 1).min(b.len());
+        for j in lo..hi {
+            if b_matches[j] || a[i] != b[j] {
+                continue;
+            }
+            a_matches[i] = true;
+            b_matches[j] = true;
+            matches += 1;
+            break;
+        }
+    }
+
+    if matches == 0 {
+        return 0.0;
+    }
+
+    let mut transpositions = 0usize;
+    let mut k = 0usize;
+    for i in 0..a.len() {
+        if !a_matches[i] {
+            continue;
+        }
+        while !b_matches[k] {
+            k += 1;
+        }
+        if a[i] != b[k] {
+            transpositions += 1;
+        }
+        k += 1;
+    }
+
+    let matches = matches as f64;
+    (matches / a.len() as f64
+        + matches / b.len() as f64
+        + (matches - transpositions as f64 / 2.0) / matches)
+        / 3.0
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -96,7 +261,7 @@ mod tests {
     #[test]
     fn test_normalization() {
         let mut normalizer = EntityNormalizer::new();
-        
+
         assert_eq!(normalizer.normalize("GraphRAG"), "graphrag");
         assert_eq!(normalizer.normalize("GraphRAG!"), "graphrag");
         assert_eq!(normalizer.normalize("  GraphRAG  "), "graphrag");
@@ -105,11 +270,34 @@ mod tests {
     #[test]
     fn test_alias_resolution() {
         let mut normalizer = EntityNormalizer::new();
-        
+
         let n1 = normalizer.normalize("OpenAI");
         let n2 = normalizer.normalize("OpenAI Inc");
-        
+
         // Should resolve to the same canonical form
         assert_eq!(n1, n2);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_short_substrings_no_longer_merge() {
+        let mut normalizer = EntityNormalizer::new();
+
+        let n1 = normalizer.normalize("AI");
+        let n2 = normalizer.normalize("Brain");
+
+        // "ai" is a substring of "brain", but "ai" is too short for the
+        // substring shortcut and the two are nowhere near similar by edit
+        // distance.
+        assert_ne!(n1, n2);
+    }
+
+    #[test]
+    fn test_typo_still_merges_via_edit_distance() {
+        let mut normalizer = EntityNormalizer::new();
+
+        let n1 = normalizer.normalize("Kubernetes");
+        let n2 = normalizer.normalize("Kubernets");
+
+        assert_eq!(n1, n2);
+    }
+}
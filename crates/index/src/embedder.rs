@@ -0,0 +1,32 @@
+use anyhow::Result;
+use async_trait::async_trait;
+
+/// Common interface for text embedding providers so callers don't have to
+/// hard-wire a specific backend (Ollama, OpenAI, TEI, ...).
+#[async_trait]
+pub trait Embedder: Send + Sync {
+    /// Embed a single piece of text. Implementations return unit-length
+    /// (L2-normalized) vectors, so callers can compare two embeddings with
+    /// a plain dot product instead of computing cosine similarity.
+    async fn embed(&self, text: &str) -> Result<Vec<f32>>;
+
+    /// Embed a batch of texts. Implementations that support a native batch
+    /// endpoint should override this; the default embeds one at a time.
+    async fn embed_batch(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        let mut embeddings = Vec::with_capacity(texts.len());
+        for text in texts {
+            embeddings.push(self.embed(text).await?);
+        }
+        Ok(embeddings)
+    }
+
+    /// Dimension of the vectors this embedder produces.
+    fn dimension(&self) -> usize;
+
+    /// Identifier for the concrete model/endpoint backing this embedder
+    /// (e.g. `"ollama:llama3"`, `"openai:text-embedding-3-small"`).
+    /// Different models produce geometrically incompatible vectors, so
+    /// callers that cache embeddings should key on this alongside the
+    /// source text to avoid mixing vectors from different providers.
+    fn model_id(&self) -> String;
+}
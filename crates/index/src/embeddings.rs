@@ -1,11 +1,46 @@
 use anyhow::{Context, Result};
+use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use common::retry::{self, RetryConfig};
+use common::ContentCache;
+use crate::embedder::Embedder;
+
+/// Ollama's default `llama3` embedding dimension.
+const DEFAULT_OLLAMA_DIMENSION: usize = 4096;
+
+/// OpenAI's `text-embedding-3-small` dimension.
+const DEFAULT_OPENAI_DIMENSION: usize = 1536;
+
+/// `BAAI/bge-small-en-v1.5`, a common default TEI model.
+const DEFAULT_TEI_DIMENSION: usize = 384;
+
+/// Default number of (model, text) -> embedding pairs kept in
+/// `EmbeddingClient`'s in-memory cache.
+const DEFAULT_EMBEDDING_CACHE_CAPACITY: usize = 10_000;
+
+/// L2-normalize a vector to unit length, so that cosine similarity between
+/// two embeddings reduces to a plain dot product. A zero vector is returned
+/// unchanged rather than dividing by zero.
+fn normalize(mut v: Vec<f32>) -> Vec<f32> {
+    let norm = v.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        for x in v.iter_mut() {
+            *x /= norm;
+        }
+    }
+    v
+}
 
 #[derive(Clone)]
 pub struct EmbeddingClient {
     base_url: String,
     model: String,
+    dimension: usize,
     client: reqwest::Client,
+    cache: Arc<ContentCache<Vec<f32>>>,
+    retry_cfg: RetryConfig,
 }
 
 #[derive(Serialize)]
@@ -20,52 +55,280 @@ struct EmbeddingResponse {
 }
 
 impl EmbeddingClient {
-    pub fn new(base_url: String, model: String) -> Self {
+    pub fn new(base_url: String, model: String, dimension: usize) -> Self {
         Self {
             base_url,
             model,
+            dimension,
             client: reqwest::Client::new(),
+            cache: Arc::new(ContentCache::new(DEFAULT_EMBEDDING_CACHE_CAPACITY)),
+            retry_cfg: RetryConfig::default(),
         }
     }
 
     pub fn default() -> Self {
         Self::new(
             "http://localhost:11434".to_string(),
-            "llama3".to_string(), 
+            "llama3".to_string(),
+            DEFAULT_OLLAMA_DIMENSION,
         )
     }
 
-    /// Generate embedding for text
-    pub async fn embed(&self, text: &str) -> Result<Vec<f32>> {
+    /// Override the embedding cache's capacity (defaults to
+    /// `DEFAULT_EMBEDDING_CACHE_CAPACITY`).
+    pub fn with_cache_capacity(mut self, capacity: usize) -> Self {
+        self.cache = Arc::new(ContentCache::new(capacity));
+        self
+    }
+
+    /// Override the retry/backoff settings used by `embed` (defaults to
+    /// `RetryConfig::default()`), so a caller can thread its own layered
+    /// retry config through instead of the hardcoded default.
+    pub fn with_retry_config(mut self, retry_cfg: RetryConfig) -> Self {
+        self.retry_cfg = retry_cfg;
+        self
+    }
+}
+
+#[async_trait]
+impl Embedder for EmbeddingClient {
+    /// Generate embedding for text, serving from the content-addressed
+    /// cache when this exact `(model, text)` pair has been embedded before.
+    async fn embed(&self, text: &str) -> Result<Vec<f32>> {
+        if let Some(cached) = self.cache.get(&self.model, text) {
+            return Ok(cached);
+        }
+
         let url = format!("{}/api/embeddings", self.base_url);
-        
-        let request = EmbeddingRequest {
-            model: self.model.clone(),
-            prompt: text.to_string(),
-        };
-
-        let response = self.client
-            .post(&url)
-            .json(&request)
-            .send()
-            .await
-            .context("Failed to send embedding request")?;
-
-        if !response.status().is_success() {
-            anyhow::bail!("Embedding request failed: {}", response.status());
+
+        let embedding_response: EmbeddingResponse = retry::retry_with_backoff(
+            &self.retry_cfg,
+            || async {
+                let request = EmbeddingRequest {
+                    model: self.model.clone(),
+                    prompt: text.to_string(),
+                };
+
+                let response = self.client
+                    .post(&url)
+                    .json(&request)
+                    .send()
+                    .await
+                    .context("Failed to send embedding request")?;
+
+                if !response.status().is_success() {
+                    return Err(anyhow::Error::new(retry::HttpStatusError(response.status()))
+                        .context(format!("Embedding request failed: {}", response.status())));
+                }
+
+                response
+                    .json()
+                    .await
+                    .context("Failed to parse embedding response")
+            },
+            retry::is_transient_error,
+        )
+        .await?;
+
+        let embedding = normalize(embedding_response.embedding);
+        self.cache.put(&self.model, text, embedding.clone());
+        Ok(embedding)
+    }
+
+    fn dimension(&self) -> usize {
+        self.dimension
+    }
+
+    fn model_id(&self) -> String {
+        format!("ollama:{}", self.model)
+    }
+}
+
+/// Embedder for any OpenAI-compatible embeddings endpoint
+/// (`POST /embeddings` with a bearer token).
+#[derive(Clone)]
+pub struct OpenAiEmbedder {
+    base_url: String,
+    api_key: String,
+    model: String,
+    dimension: usize,
+    client: reqwest::Client,
+}
+
+#[derive(Serialize)]
+struct OpenAiEmbeddingRequest {
+    input: Vec<String>,
+    model: String,
+}
+
+#[derive(Deserialize)]
+struct OpenAiEmbeddingResponse {
+    data: Vec<OpenAiEmbeddingData>,
+}
+
+#[derive(Deserialize)]
+struct OpenAiEmbeddingData {
+    embedding: Vec<f32>,
+}
+
+impl OpenAiEmbedder {
+    pub fn new(base_url: String, api_key: String, model: String, dimension: usize) -> Self {
+        Self {
+            base_url,
+            api_key,
+            model,
+            dimension,
+            client: reqwest::Client::new(),
         }
+    }
 
-        let embedding_response: EmbeddingResponse = response
-            .json()
-            .await
-            .context("Failed to parse embedding response")?;
+    pub fn default() -> Self {
+        Self::new(
+            "https://api.openai.com/v1".to_string(),
+            String::new(),
+            "text-embedding-3-small".to_string(),
+            DEFAULT_OPENAI_DIMENSION,
+        )
+    }
+
+    async fn embed_all(&self, texts: Vec<String>) -> Result<Vec<Vec<f32>>> {
+        let url = format!("{}/embeddings", self.base_url);
+        let retry_cfg = RetryConfig::default();
+
+        let embedding_response: OpenAiEmbeddingResponse = retry::retry_with_backoff(
+            &retry_cfg,
+            || async {
+                let request = OpenAiEmbeddingRequest {
+                    input: texts.clone(),
+                    model: self.model.clone(),
+                };
+
+                let response = self.client
+                    .post(&url)
+                    .bearer_auth(&self.api_key)
+                    .json(&request)
+                    .send()
+                    .await
+                    .context("Failed to send embedding request")?;
+
+                if !response.status().is_success() {
+                    return Err(anyhow::Error::new(retry::HttpStatusError(response.status()))
+                        .context(format!("OpenAI embedding request failed: {}", response.status())));
+                }
+
+                response
+                    .json()
+                    .await
+                    .context("Failed to parse OpenAI embedding response")
+            },
+            retry::is_transient_error,
+        )
+        .await?;
+
+        Ok(embedding_response
+            .data
+            .into_iter()
+            .map(|d| normalize(d.embedding))
+            .collect())
+    }
+}
+
+#[async_trait]
+impl Embedder for OpenAiEmbedder {
+    async fn embed(&self, text: &str) -> Result<Vec<f32>> {
+        let mut embeddings = self.embed_all(vec![text.to_string()]).await?;
+        embeddings.pop().context("OpenAI returned no embeddings")
+    }
+
+    async fn embed_batch(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        self.embed_all(texts.to_vec()).await
+    }
+
+    fn dimension(&self) -> usize {
+        self.dimension
+    }
+
+    fn model_id(&self) -> String {
+        format!("openai:{}", self.model)
+    }
+}
+
+/// Embedder for a local HuggingFace `text-embeddings-inference` (TEI) server.
+#[derive(Clone)]
+pub struct TeiEmbedder {
+    base_url: String,
+    dimension: usize,
+    client: reqwest::Client,
+}
+
+#[derive(Serialize)]
+struct TeiEmbeddingRequest {
+    inputs: Vec<String>,
+}
+
+impl TeiEmbedder {
+    pub fn new(base_url: String, dimension: usize) -> Self {
+        Self {
+            base_url,
+            dimension,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    pub fn default() -> Self {
+        Self::new("http://localhost:8080".to_string(), DEFAULT_TEI_DIMENSION)
+    }
+
+    async fn embed_all(&self, texts: Vec<String>) -> Result<Vec<Vec<f32>>> {
+        let url = format!("{}/embed", self.base_url);
+        let retry_cfg = RetryConfig::default();
+
+        let embeddings: Vec<Vec<f32>> = retry::retry_with_backoff(
+            &retry_cfg,
+            || async {
+                let request = TeiEmbeddingRequest { inputs: texts.clone() };
+
+                let response = self.client
+                    .post(&url)
+                    .json(&request)
+                    .send()
+                    .await
+                    .context("Failed to send embedding request")?;
+
+                if !response.status().is_success() {
+                    return Err(anyhow::Error::new(retry::HttpStatusError(response.status()))
+                        .context(format!("TEI embedding request failed: {}", response.status())));
+                }
+
+                response
+                    .json::<Vec<Vec<f32>>>()
+                    .await
+                    .context("Failed to parse TEI embedding response")
+            },
+            retry::is_transient_error,
+        )
+        .await?;
+
+        Ok(embeddings.into_iter().map(normalize).collect())
+    }
+}
+
+#[async_trait]
+impl Embedder for TeiEmbedder {
+    async fn embed(&self, text: &str) -> Result<Vec<f32>> {
+        let mut embeddings = self.embed_all(vec![text.to_string()]).await?;
+        embeddings.pop().context("TEI returned no embeddings")
+    }
+
+    async fn embed_batch(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        self.embed_all(texts.to_vec()).await
+    }
 
-        Ok(embedding_response.embedding)
+    fn dimension(&self) -> usize {
+        self.dimension
     }
 
-    /// Get embedding dimension
-    pub async fn get_dimension(&self) -> Result<usize> {
-        let test_embedding = self.embed("test").await?;
-        Ok(test_embedding.len())
+    fn model_id(&self) -> String {
+        format!("tei:{}", self.base_url)
     }
 }
\ No newline at end of file
@@ -13,17 +13,23 @@ mod tests {
     }
 }
 
+pub mod embedder;
 pub mod embeddings;
 pub mod qdrant_index;
 pub mod neo4j_index;
 
-pub use embeddings::EmbeddingClient;
-pub use qdrant_index::QdrantIndexer;
+pub use embedder::Embedder;
+pub use embeddings::{EmbeddingClient, OpenAiEmbedder, TeiEmbedder};
+pub use qdrant_index::{ChunkIndexResult, QdrantIndexer};
 pub use neo4j_index::{Neo4jIndexer, GraphStats};
 
 use anyhow::Result;
 //use std::path::Path;
 
+/// Qdrant-side batch size that `Indexer::index_batch` passes to
+/// `QdrantIndexer::index_chunks`.
+const DEFAULT_INDEX_BATCH_SIZE: usize = 50;
+
 /// Unified indexer that handles both Qdrant and Neo4j
 pub struct Indexer {
     qdrant: QdrantIndexer,
@@ -53,21 +59,67 @@ impl Indexer {
         chunk: &ingest::Chunk,
         extracted: &extract::ExtractedChunk,
     ) -> Result<()> {
-        // Extract entity IDs
+        // Index in Neo4j (graph store) first so we know which canonical,
+        // content-addressed ID each chunk-local entity was merged under.
+        let local_to_canonical = self.neo4j.index_extraction(&extracted.extraction).await?;
+
+        // Use those canonical IDs for Qdrant too, so a chunk's stored
+        // entity_ids point at the same merged nodes the graph side has.
         let entity_ids: Vec<String> = extracted.extraction.entities
             .iter()
-            .map(|e| e.id.clone())
+            .map(|e| {
+                local_to_canonical
+                    .get(&e.id)
+                    .cloned()
+                    .unwrap_or_else(|| e.id.clone())
+            })
             .collect();
 
         // Index in Qdrant (vector store)
         self.qdrant.index_chunk(chunk, entity_ids).await?;
 
-        // Index in Neo4j (graph store)
-        self.neo4j.index_extraction(&extracted.extraction).await?;
-
         Ok(())
     }
 
+    /// Index many extracted chunks as one batch. Unlike
+    /// `index_extracted_chunk`'s strictly-sequential Neo4j-then-Qdrant write,
+    /// this computes each chunk's canonical `entity_ids` up front (a pure
+    /// function of entity name+type, see `neo4j_index::local_to_canonical`)
+    /// and runs the Qdrant and Neo4j halves of the batch concurrently, each
+    /// internally chunked into bounded-size, bounded-concurrency writes so a
+    /// large ingest doesn't open thousands of simultaneous requests at once.
+    pub async fn index_batch(
+        &self,
+        items: &[(ingest::Chunk, extract::ExtractedChunk)],
+    ) -> Result<Vec<ChunkIndexResult>> {
+        let qdrant_items: Vec<(ingest::Chunk, Vec<String>)> = items
+            .iter()
+            .map(|(chunk, extracted)| {
+                let canonical = neo4j_index::local_to_canonical(&extracted.extraction);
+                let entity_ids = extracted
+                    .extraction
+                    .entities
+                    .iter()
+                    .map(|e| canonical.get(&e.id).cloned().unwrap_or_else(|| e.id.clone()))
+                    .collect();
+                (chunk.clone(), entity_ids)
+            })
+            .collect();
+
+        let extractions: Vec<&extract::ExtractionResult> = items
+            .iter()
+            .map(|(_, extracted)| &extracted.extraction)
+            .collect();
+
+        let (qdrant_result, neo4j_result) = tokio::join!(
+            self.qdrant.index_chunks(&qdrant_items, DEFAULT_INDEX_BATCH_SIZE),
+            self.neo4j.index_extractions(&extractions),
+        );
+
+        neo4j_result?;
+        qdrant_result
+    }
+
     /// Get overall stats
     pub async fn get_stats(&self) -> Result<IndexStats> {
         let graph_stats = self.neo4j.get_stats().await?;
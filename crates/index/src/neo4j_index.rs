@@ -1,10 +1,59 @@
 use anyhow::{Context, Result};
+use futures::stream::{self, StreamExt};
 use neo4rs::{Graph, Query};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+
+/// How many extractions `index_extractions` writes to Neo4j concurrently,
+/// mirroring `QdrantIndexer`'s batch concurrency so a large ingest doesn't
+/// open thousands of simultaneous Cypher sessions at once.
+const DEFAULT_EXTRACTION_CONCURRENCY: usize = 4;
 
 pub struct Neo4jIndexer {
     graph: Graph,
 }
 
+/// Content-address an entity: hash a normalized `name`+`type` key so the
+/// same real-world entity mentioned across many chunks collapses onto one
+/// node, instead of fragmenting across the LLM's ephemeral per-chunk `E#`
+/// IDs. Hashes the same way `Cache::hash_text` hashes cache keys.
+fn canonical_entity_id(entity: &extract::Entity) -> String {
+    let key = format!(
+        "{}:{}",
+        normalize_key(&entity.name),
+        normalize_key(&entity.entity_type)
+    );
+    let mut hasher = Sha256::new();
+    hasher.update(key.as_bytes());
+    hex::encode(hasher.finalize())
+}
+
+/// Map each chunk-local `E#` entity ID to its canonical, content-addressed
+/// ID. Unlike `index_extraction`, this does no I/O: `canonical_entity_id` is
+/// a pure function of the entity's name+type, so a caller that only needs
+/// the mapping (e.g. to rewire a chunk's stored `entity_ids` for Qdrant) can
+/// compute it without waiting on the Neo4j write to report it back.
+pub fn local_to_canonical(extraction: &extract::ExtractionResult) -> HashMap<String, String> {
+    extraction
+        .entities
+        .iter()
+        .map(|e| (e.id.clone(), canonical_entity_id(e)))
+        .collect()
+}
+
+/// Lowercase, trim, and strip punctuation so trivially different spellings
+/// of the same name ("OpenAI.", "openai") hash to the same canonical ID.
+fn normalize_key(s: &str) -> String {
+    s.trim()
+        .to_lowercase()
+        .chars()
+        .filter(|c| c.is_alphanumeric() || c.is_whitespace())
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
 impl Neo4jIndexer {
     pub fn new(graph: Graph) -> Self {
         Self { graph }
@@ -32,17 +81,27 @@ impl Neo4jIndexer {
         Ok(())
     }
 
-    /// Index an entity (MERGE to avoid duplicates)
+    /// Index an entity under its content-addressed canonical ID (MERGE to
+    /// avoid duplicates across chunks). On a repeat mention, the description
+    /// is accumulated rather than overwritten, so the merged node ends up
+    /// with richer context than any single chunk's mention of it.
     pub async fn index_entity(&self, entity: &extract::Entity) -> Result<()> {
+        let canonical_id = canonical_entity_id(entity);
+
         let query = Query::new(
             r#"
             MERGE (e:Entity {id: $id})
-            SET e.name = $name,
-                e.type = $type,
-                e.description = $description
+            ON CREATE SET e.name = $name,
+                          e.type = $type,
+                          e.description = $description
+            ON MATCH SET e.description = CASE
+                WHEN $description <> '' AND NOT e.description CONTAINS $description
+                THEN e.description + ' ' + $description
+                ELSE e.description
+            END
             "#.to_string()
         )
-        .param("id", entity.id.clone())
+        .param("id", canonical_id)
         .param("name", entity.name.clone())
         .param("type", entity.entity_type.clone())
         .param("description", entity.description.clone());
@@ -95,19 +154,56 @@ impl Neo4jIndexer {
         Ok(())
     }
 
-    /// Batch index extracted data
+    /// Batch index extracted data. Returns the map from this chunk's local
+    /// `E#` entity IDs to the canonical IDs they were merged under, so
+    /// callers (e.g. the Qdrant side of indexing) can rewire their own
+    /// references to the same merged nodes.
     pub async fn index_extraction(
         &self,
         extraction: &extract::ExtractionResult,
-    ) -> Result<()> {
-        // Index all entities
+    ) -> Result<HashMap<String, String>> {
+        // Index all entities, recording how each chunk-local ID maps to its
+        // canonical, content-addressed ID.
+        let local_to_canonical = local_to_canonical(extraction);
         for entity in &extraction.entities {
             self.index_entity(entity).await?;
         }
 
-        // Index all relations
+        // Rewire relations onto the canonical IDs before indexing them, so
+        // they connect the merged nodes rather than chunk-local stand-ins.
         for relation in &extraction.relations {
-            self.index_relation(relation).await?;
+            let source = local_to_canonical
+                .get(&relation.source)
+                .cloned()
+                .unwrap_or_else(|| relation.source.clone());
+            let target = local_to_canonical
+                .get(&relation.target)
+                .cloned()
+                .unwrap_or_else(|| relation.target.clone());
+
+            let rewired = extract::Relation {
+                source,
+                target,
+                relation: relation.relation.clone(),
+                evidence: relation.evidence.clone(),
+            };
+            self.index_relation(&rewired).await?;
+        }
+
+        Ok(local_to_canonical)
+    }
+
+    /// Index many extractions concurrently, bounded to
+    /// `DEFAULT_EXTRACTION_CONCURRENCY` in flight, instead of the one-at-a-time
+    /// sequencing `index_extraction` does on its own. Bails on the first
+    /// failing extraction, same as calling `index_extraction` in a loop would.
+    pub async fn index_extractions(&self, extractions: &[&extract::ExtractionResult]) -> Result<()> {
+        let mut results = stream::iter(extractions.iter())
+            .map(|extraction| self.index_extraction(extraction))
+            .buffer_unordered(DEFAULT_EXTRACTION_CONCURRENCY);
+
+        while let Some(result) = results.next().await {
+            result?;
         }
 
         Ok(())
@@ -1,19 +1,31 @@
 use anyhow::{Context, Result};
+use futures::stream::{self, StreamExt};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-use crate::embeddings::EmbeddingClient;
+use crate::embedder::Embedder;
+
+/// Name of the dense named vector in the Qdrant collection.
+const DENSE_VECTOR_NAME: &str = "dense";
+
+/// Name of the sparse named vector in the Qdrant collection.
+const SPARSE_VECTOR_NAME: &str = "sparse";
+
+/// How many embedding batches (each up to `index_chunks`'s `batch_size`
+/// chunks) are embedded and upserted concurrently.
+const DEFAULT_BATCH_CONCURRENCY: usize = 4;
 
 pub struct QdrantIndexer {
     base_url: String,
     client: reqwest::Client,
-    embedding_client: EmbeddingClient,
+    embedder: Box<dyn Embedder>,
     collection_name: String,
 }
 
 #[derive(Serialize)]
 struct CreateCollection {
-    vectors: VectorParams,
+    vectors: HashMap<String, VectorParams>,
+    sparse_vectors: HashMap<String, SparseVectorParams>,
 }
 
 #[derive(Serialize)]
@@ -22,6 +34,9 @@ struct VectorParams {
     distance: String,
 }
 
+#[derive(Serialize)]
+struct SparseVectorParams {}
+
 #[derive(Serialize)]
 struct UpsertPoints {
     points: Vec<Point>,
@@ -30,10 +45,25 @@ struct UpsertPoints {
 #[derive(Serialize)]
 struct Point {
     id: u64,
-    vector: Vec<f32>,
+    vector: PointVectors,
     payload: HashMap<String, serde_json::Value>,
 }
 
+#[derive(Serialize)]
+struct PointVectors {
+    #[serde(rename = "dense")]
+    dense: Vec<f32>,
+    #[serde(rename = "sparse")]
+    sparse: SparseVector,
+}
+
+/// A sparse vector in Qdrant's `{indices, values}` representation.
+#[derive(Serialize)]
+struct SparseVector {
+    indices: Vec<u32>,
+    values: Vec<f32>,
+}
+
 #[derive(Deserialize)]
 struct CollectionInfo {
     result: CollectionResult,
@@ -49,16 +79,25 @@ struct Collection {
     name: String,
 }
 
+/// Outcome of indexing a single chunk as part of a batch submitted to
+/// `QdrantIndexer::index_chunks`.
+#[derive(Debug, Serialize)]
+pub struct ChunkIndexResult {
+    pub chunk_id: String,
+    pub success: bool,
+    pub error: Option<String>,
+}
+
 impl QdrantIndexer {
     pub fn new(
         base_url: String,
-        embedding_client: EmbeddingClient,
+        embedder: Box<dyn Embedder>,
         collection_name: String,
     ) -> Self {
         Self {
             base_url,
             client: reqwest::Client::new(),
-            embedding_client,
+            embedder,
             collection_name,
         }
     }
@@ -83,16 +122,27 @@ impl QdrantIndexer {
         }
 
         // Get embedding dimension
-        let dimension = self.embedding_client.get_dimension().await?;
+        let dimension = self.embedder.dimension();
         println!("Creating collection with dimension: {}", dimension);
 
-        // Create collection
+        // Create collection with both a dense named vector and a sparse
+        // named vector, so retrieval can issue a server-side prefetch+fusion
+        // hybrid query instead of pulling full candidate sets back to the client.
         let url = format!("{}/collections/{}", self.base_url, self.collection_name);
-        let create_req = CreateCollection {
-            vectors: VectorParams {
+        let mut vectors = HashMap::new();
+        vectors.insert(
+            DENSE_VECTOR_NAME.to_string(),
+            VectorParams {
                 size: dimension,
                 distance: "Cosine".to_string(),
             },
+        );
+        let mut sparse_vectors = HashMap::new();
+        sparse_vectors.insert(SPARSE_VECTOR_NAME.to_string(), SparseVectorParams {});
+
+        let create_req = CreateCollection {
+            vectors,
+            sparse_vectors,
         };
 
         let response = self.client
@@ -116,13 +166,87 @@ impl QdrantIndexer {
         chunk: &ingest::Chunk,
         entity_ids: Vec<String>,
     ) -> Result<()> {
-        // Generate embedding
-        let embedding = self.embedding_client
+        let embedding = self.embedder
             .embed(&chunk.text)
             .await
             .context("Failed to generate embedding")?;
 
-        // Build payload
+        let point = self.build_point(chunk, &entity_ids, embedding);
+        self.upsert_points(vec![point]).await
+    }
+
+    /// Index many chunks at once. Chunks are split into `batch_size`-sized
+    /// groups, each embedded with a single `Embedder::embed_batch` call and
+    /// upserted with a single Qdrant request, with up to
+    /// `DEFAULT_BATCH_CONCURRENCY` groups embedding/upserting concurrently.
+    ///
+    /// A failure embedding or upserting one group only fails that group's
+    /// chunks; the rest of the ingest still runs to completion.
+    pub async fn index_chunks(
+        &self,
+        chunks: &[(ingest::Chunk, Vec<String>)],
+        batch_size: usize,
+    ) -> Result<Vec<ChunkIndexResult>> {
+        let batch_size = batch_size.max(1);
+
+        let results: Vec<Vec<ChunkIndexResult>> = stream::iter(chunks.chunks(batch_size))
+            .map(|batch| self.index_batch(batch))
+            .buffer_unordered(DEFAULT_BATCH_CONCURRENCY)
+            .collect()
+            .await;
+
+        Ok(results.into_iter().flatten().collect())
+    }
+
+    /// Embed and upsert a single batch, returning one result per chunk.
+    async fn index_batch(&self, batch: &[(ingest::Chunk, Vec<String>)]) -> Vec<ChunkIndexResult> {
+        let texts: Vec<String> = batch.iter().map(|(chunk, _)| chunk.text.clone()).collect();
+
+        let embeddings = match self.embedder.embed_batch(&texts).await {
+            Ok(embeddings) => embeddings,
+            Err(e) => {
+                return batch
+                    .iter()
+                    .map(|(chunk, _)| ChunkIndexResult {
+                        chunk_id: chunk.chunk_id.clone(),
+                        success: false,
+                        error: Some(format!("Failed to generate embeddings: {}", e)),
+                    })
+                    .collect();
+            }
+        };
+
+        let points: Vec<Point> = batch
+            .iter()
+            .zip(embeddings)
+            .map(|((chunk, entity_ids), embedding)| self.build_point(chunk, entity_ids, embedding))
+            .collect();
+
+        match self.upsert_points(points).await {
+            Ok(()) => batch
+                .iter()
+                .map(|(chunk, _)| ChunkIndexResult {
+                    chunk_id: chunk.chunk_id.clone(),
+                    success: true,
+                    error: None,
+                })
+                .collect(),
+            Err(e) => batch
+                .iter()
+                .map(|(chunk, _)| ChunkIndexResult {
+                    chunk_id: chunk.chunk_id.clone(),
+                    success: false,
+                    error: Some(format!("Failed to upsert batch: {}", e)),
+                })
+                .collect(),
+        }
+    }
+
+    /// Build a Qdrant point from a chunk, its entity IDs, and a precomputed
+    /// dense embedding (the sparse vector is derived from the chunk text).
+    fn build_point(&self, chunk: &ingest::Chunk, entity_ids: &[String], embedding: Vec<f32>) -> Point {
+        let sparse_vector = Self::sparse_vector_for(&chunk.text);
+
         let mut payload = HashMap::new();
         payload.insert(
             "chunk_id".to_string(),
@@ -145,25 +269,24 @@ impl QdrantIndexer {
             serde_json::json!(entity_ids.join(",")),
         );
 
-        // Use chunk_id as point ID (hash to u64)
-        let point_id = self.hash_to_u64(&chunk.chunk_id);
-
-        // Create point
-        let point = Point {
-            id: point_id,
-            vector: embedding,
+        Point {
+            id: self.hash_to_u64(&chunk.chunk_id),
+            vector: PointVectors {
+                dense: embedding,
+                sparse: sparse_vector,
+            },
             payload,
-        };
+        }
+    }
 
-        // Upsert point
+    /// Upsert one or more points in a single Qdrant request.
+    async fn upsert_points(&self, points: Vec<Point>) -> Result<()> {
         let url = format!(
             "{}/collections/{}/points",
             self.base_url, self.collection_name
         );
-        
-        let upsert_req = UpsertPoints {
-            points: vec![point],
-        };
+
+        let upsert_req = UpsertPoints { points };
 
         let response = self.client
             .put(&url)
@@ -173,7 +296,7 @@ impl QdrantIndexer {
 
         if !response.status().is_success() {
             let error_text = response.text().await?;
-            anyhow::bail!("Failed to upsert point: {}", error_text);
+            anyhow::bail!("Failed to upsert points: {}", error_text);
         }
 
         Ok(())
@@ -183,12 +306,48 @@ impl QdrantIndexer {
     fn hash_to_u64(&self, s: &str) -> u64 {
         use std::collections::hash_map::DefaultHasher;
         use std::hash::{Hash, Hasher};
-        
+
         let mut hasher = DefaultHasher::new();
         s.hash(&mut hasher);
         hasher.finish()
     }
 
+    /// Build a TF-weighted sparse vector from the chunk text, hashing each
+    /// token to a u32 index so it can be stored as a Qdrant sparse vector.
+    fn sparse_vector_for(text: &str) -> SparseVector {
+        let mut term_counts: HashMap<u32, f32> = HashMap::new();
+
+        for token in text.to_lowercase().split(|c: char| !c.is_alphanumeric()) {
+            if token.is_empty() {
+                continue;
+            }
+            let index = Self::hash_token_to_u32(token);
+            *term_counts.entry(index).or_insert(0.0) += 1.0;
+        }
+
+        let doc_len = term_counts.values().sum::<f32>().max(1.0);
+        let mut indices = Vec::with_capacity(term_counts.len());
+        let mut values = Vec::with_capacity(term_counts.len());
+
+        for (index, count) in term_counts {
+            indices.push(index);
+            // Sublinear TF weighting (1 + ln(tf)), normalized by chunk length.
+            values.push((1.0 + count.ln()) / doc_len);
+        }
+
+        SparseVector { indices, values }
+    }
+
+    /// Hash a token to a u32 sparse-vector index.
+    fn hash_token_to_u32(token: &str) -> u32 {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::{Hash, Hasher};
+
+        let mut hasher = DefaultHasher::new();
+        token.hash(&mut hasher);
+        hasher.finish() as u32
+    }
+
     /// Get collection info
     pub async fn collection_info(&self) -> Result<()> {
         let url = format!(
@@ -0,0 +1,96 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::chunk::Chunk;
+
+/// Where a chunk's extraction stands, keyed by content hash in
+/// `Checkpoint::chunks`. A crash between `mark_in_flight` and
+/// `mark_completed` leaves a chunk `InFlight`, which is exactly the state
+/// `resume` treats as unfinished work rather than silently-skipped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ChunkState {
+    InFlight,
+    Completed,
+}
+
+/// Durable manifest mapping a chunk's content hash to its extraction
+/// state, so indexing a large corpus can be interrupted and resumed:
+/// chunks whose hash is already `Completed` are skipped, chunks left
+/// `InFlight` from a prior crash are re-run, and brand-new hashes are
+/// processed for the first time.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Checkpoint {
+    chunks: HashMap<String, ChunkState>,
+}
+
+impl Checkpoint {
+    /// Load a checkpoint from disk, or an empty one if it doesn't exist yet
+    /// (e.g. the first run over a corpus).
+    pub async fn load(path: &Path) -> Result<Self> {
+        match tokio::fs::read_to_string(path).await {
+            Ok(raw) => serde_json::from_str(&raw).context("Failed to parse checkpoint"),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e).context(format!("Failed to read checkpoint: {:?}", path)),
+        }
+    }
+
+    pub async fn save(&self, path: &Path) -> Result<()> {
+        let raw = serde_json::to_string_pretty(self)?;
+        tokio::fs::write(path, raw)
+            .await
+            .context(format!("Failed to write checkpoint: {:?}", path))
+    }
+
+    /// Record that `content_hash`'s extraction has started, so a crash
+    /// before the matching `mark_completed` leaves it for `resume` to
+    /// retry instead of silently dropping it.
+    pub fn mark_in_flight(&mut self, content_hash: &str) {
+        self.chunks
+            .insert(content_hash.to_string(), ChunkState::InFlight);
+    }
+
+    /// Record that `content_hash`'s extraction finished. Called only after
+    /// the extraction (and its index write) is durably committed.
+    pub fn mark_completed(&mut self, content_hash: &str) {
+        self.chunks
+            .insert(content_hash.to_string(), ChunkState::Completed);
+    }
+
+    pub fn is_completed(&self, content_hash: &str) -> bool {
+        matches!(self.chunks.get(content_hash), Some(ChunkState::Completed))
+    }
+
+    pub fn is_in_flight(&self, content_hash: &str) -> bool {
+        matches!(self.chunks.get(content_hash), Some(ChunkState::InFlight))
+    }
+}
+
+/// The chunk's identity for checkpointing purposes: its stable
+/// `content_checksum` when `content_hashing` is enabled, so an unmoved,
+/// unedited chunk is recognized as the same work even if it was re-chunked
+/// with a different offset; otherwise its `chunk_id`, which also folds in
+/// `doc_id` and offset and so treats any reshuffle as new work.
+fn checkpoint_key(chunk: &Chunk, content_hashing: bool) -> &str {
+    if content_hashing {
+        &chunk.content_checksum
+    } else {
+        &chunk.chunk_id
+    }
+}
+
+/// Diff `chunks` against `checkpoint`, returning the chunks `resume`
+/// should (re-)process: anything not yet marked `Completed`, plus anything
+/// left `InFlight` by a run that crashed mid-chunk. Chunks already
+/// `Completed` are left out entirely.
+pub fn resume<'a>(
+    checkpoint: &Checkpoint,
+    chunks: &'a [Chunk],
+    content_hashing: bool,
+) -> Vec<&'a Chunk> {
+    chunks
+        .iter()
+        .filter(|chunk| !checkpoint.is_completed(checkpoint_key(chunk, content_hashing)))
+        .collect()
+}
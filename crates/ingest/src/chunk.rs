@@ -8,6 +8,9 @@ pub struct Chunk {
     pub text: String,
     pub source: String,
     pub offset: (usize, usize), // [start, end] character positions
+    /// SHA-256 of the chunk's text content, so downstream storage can
+    /// detect corruption or dedupe identical chunks.
+    pub content_checksum: String,
 }
 
 impl Chunk {
@@ -19,13 +22,15 @@ impl Chunk {
     ) -> Self {
         // Generate stable chunk_id from content
         let chunk_id = Self::generate_chunk_id(&doc_id, &text, offset);
-        
+        let content_checksum = Self::generate_content_checksum(&text);
+
         Self {
             doc_id,
             chunk_id,
             text,
             source,
             offset,
+            content_checksum,
         }
     }
 
@@ -39,6 +44,12 @@ impl Chunk {
         hex::encode(&result[..16]) // Use first 16 bytes (32 hex chars)
     }
 
+    fn generate_content_checksum(text: &str) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(text.as_bytes());
+        hex::encode(hasher.finalize())
+    }
+
     /// Estimate token count (rough: 1.3 tokens per word)
     pub fn estimated_tokens(&self) -> usize {
         let word_count = self.text.split_whitespace().count();
@@ -1,10 +1,29 @@
 //use unicode_segmentation::UnicodeSegmentation;
+use async_stream::stream;
+use futures::Stream;
+use std::sync::Arc;
+use tokio::io::{AsyncRead, AsyncReadExt};
+
 use crate::chunk::Chunk;
+use crate::code_chunker::{self, CodeLanguage};
+use crate::token_counter::{HeuristicTokenCounter, TokenCounter};
+
+/// Size of each fixed-size byte window read from a streaming source.
+const STREAM_WINDOW_BYTES: usize = 64 * 1024;
 
 pub struct ChunkerConfig {
     pub target_tokens_min: usize,
     pub target_tokens_max: usize,
     pub overlap_tokens: usize,
+    /// Language to parse source files as for syntax-aware chunking. `None`
+    /// auto-detects from the `source` file extension passed to
+    /// `chunk_text`, falling back to prose-style chunking when neither
+    /// finds a recognized language.
+    pub language: Option<CodeLanguage>,
+    /// Tokenizer used to size chunks and slice overlap. Defaults to the
+    /// word-count heuristic; set this to a `BpeTokenCounter` for true
+    /// model token counts.
+    pub token_counter: Arc<dyn TokenCounter>,
 }
 
 impl Default for ChunkerConfig {
@@ -13,6 +32,8 @@ impl Default for ChunkerConfig {
             target_tokens_min: 700,
             target_tokens_max: 900,
             overlap_tokens: 100,
+            language: None,
+            token_counter: Arc::new(HeuristicTokenCounter),
         }
     }
 }
@@ -31,9 +52,25 @@ impl Chunker {
         doc_id: &str,
         text: &str,
         source: &str,
+    ) -> Vec<Chunk> {
+        let language = self.config.language.or_else(|| CodeLanguage::from_extension(source));
+        if let Some(language) = language {
+            if let Some(chunks) = self.chunk_code(doc_id, text, source, language) {
+                return chunks;
+            }
+        }
+
+        self.chunk_prose(doc_id, text, source)
+    }
+
+    fn chunk_prose(
+        &self,
+        doc_id: &str,
+        text: &str,
+        source: &str,
     ) -> Vec<Chunk> {
         let mut chunks = Vec::new();
-        
+
         // Split by headings first (markdown and plain text)
         let sections = self.split_by_headings(text);
         
@@ -98,6 +135,101 @@ impl Chunker {
         chunks
     }
 
+    /// Parse `text` as `language` and carve chunks along top-level syntax
+    /// node boundaries (functions, impl blocks, classes, ...), packing
+    /// whole units into a chunk up to `target_tokens_max` and only falling
+    /// back to line-splitting when a single unit exceeds the budget on its
+    /// own. Returns `None` if the source fails to parse, so the caller can
+    /// fall back to prose-style chunking.
+    fn chunk_code(
+        &self,
+        doc_id: &str,
+        text: &str,
+        source: &str,
+        language: CodeLanguage,
+    ) -> Option<Vec<Chunk>> {
+        let tree = code_chunker::parse(text, language)?;
+        let root = tree.root_node();
+
+        let mut chunks = Vec::new();
+        let mut buffer: Option<(usize, usize)> = None;
+
+        let mut cursor = root.walk();
+        for node in root.children(&mut cursor) {
+            let node_start = node.start_byte();
+            let node_end = node.end_byte();
+
+            if self.estimate_tokens(&text[node_start..node_end]) > self.config.target_tokens_max {
+                if let Some((start, end)) = buffer.take() {
+                    chunks.push(self.build_chunk(doc_id, text, source, start, end));
+                }
+                chunks.extend(self.chunk_lines(doc_id, &text[node_start..node_end], source, node_start));
+                continue;
+            }
+
+            buffer = Some(match buffer {
+                None => (node_start, node_end),
+                Some((start, _)) => {
+                    if self.estimate_tokens(&text[start..node_end]) > self.config.target_tokens_max {
+                        let (prev_start, prev_end) = buffer.expect("buffer is Some in this branch");
+                        chunks.push(self.build_chunk(doc_id, text, source, prev_start, prev_end));
+                        (node_start, node_end)
+                    } else {
+                        (start, node_end)
+                    }
+                }
+            });
+        }
+
+        if let Some((start, end)) = buffer {
+            chunks.push(self.build_chunk(doc_id, text, source, start, end));
+        }
+
+        Some(chunks)
+    }
+
+    /// Fall back to packing a single oversized syntax node line-by-line up
+    /// to `target_tokens_max`, since splitting mid-line would be worse.
+    fn chunk_lines(&self, doc_id: &str, text: &str, source: &str, base_offset: usize) -> Vec<Chunk> {
+        let mut chunks = Vec::new();
+        let mut buffer = String::new();
+        let mut buffer_start = base_offset;
+        let mut offset = base_offset;
+
+        for line in text.split_inclusive('\n') {
+            if !buffer.is_empty()
+                && self.estimate_tokens(&buffer) + self.estimate_tokens(line) > self.config.target_tokens_max
+            {
+                chunks.push(Chunk::new(
+                    doc_id.to_string(),
+                    buffer.clone(),
+                    source.to_string(),
+                    (buffer_start, offset),
+                ));
+                buffer.clear();
+                buffer_start = offset;
+            }
+
+            buffer.push_str(line);
+            offset += line.len();
+        }
+
+        if !buffer.trim().is_empty() {
+            chunks.push(Chunk::new(doc_id.to_string(), buffer, source.to_string(), (buffer_start, offset)));
+        }
+
+        chunks
+    }
+
+    fn build_chunk(&self, doc_id: &str, text: &str, source: &str, start: usize, end: usize) -> Chunk {
+        Chunk::new(
+            doc_id.to_string(),
+            text[start..end].to_string(),
+            source.to_string(),
+            (start, end),
+        )
+    }
+
     fn split_by_headings(&self, text: &str) -> Vec<String> {
         let mut sections = Vec::new();
         let mut current_section = String::new();
@@ -133,23 +265,130 @@ impl Chunker {
     }
 
     fn estimate_tokens(&self, text: &str) -> usize {
-        let word_count = text.split_whitespace().count();
-        (word_count as f64 * 1.3) as usize
+        self.config.token_counter.count_tokens(text)
     }
 
     fn get_overlap(&self, text: &str, target_tokens: usize) -> String {
-        let words: Vec<&str> = text.split_whitespace().collect();
-        let target_words = (target_tokens as f64 / 1.3) as usize;
-        
-        if words.len() <= target_words {
-            return text.to_string();
+        self.config.token_counter.tail(text, target_tokens)
+    }
+
+    /// Stream chunks from `reader` without loading the whole document into
+    /// memory: reads fixed-size byte windows, decodes incrementally on
+    /// UTF-8 boundaries, and emits a `Chunk` as soon as the sliding buffer
+    /// holds enough text (plus the configured overlap) to flush. Running
+    /// char offsets are tracked across window boundaries so `Chunk.offset`
+    /// stays correct regardless of how the source was split into windows.
+    pub fn chunk_stream<R>(
+        &self,
+        doc_id: String,
+        source: String,
+        mut reader: R,
+    ) -> impl Stream<Item = Chunk>
+    where
+        R: AsyncRead + Unpin + Send + 'static,
+    {
+        let target_tokens_max = self.config.target_tokens_max;
+        let overlap_tokens = self.config.overlap_tokens;
+        let token_counter = self.config.token_counter.clone();
+
+        stream! {
+            let mut pending_bytes: Vec<u8> = Vec::new();
+            let mut buffer = String::new();
+            let mut buffer_start = 0usize;
+            let mut read_buf = vec![0u8; STREAM_WINDOW_BYTES];
+
+            loop {
+                let n = match reader.read(&mut read_buf).await {
+                    Ok(0) => {
+                        // EOF: decode any trailing bytes (lossily, in case the
+                        // source ended mid-codepoint) and flush what's left.
+                        if !pending_bytes.is_empty() {
+                            buffer.push_str(&String::from_utf8_lossy(&pending_bytes));
+                            pending_bytes.clear();
+                        }
+                        if !buffer.trim().is_empty() {
+                            let chunk_len = buffer.chars().count();
+                            yield Chunk::new(
+                                doc_id.clone(),
+                                buffer.clone(),
+                                source.clone(),
+                                (buffer_start, buffer_start + chunk_len),
+                            );
+                        }
+                        break;
+                    }
+                    Ok(n) => n,
+                    Err(_) => break,
+                };
+
+                pending_bytes.extend_from_slice(&read_buf[..n]);
+
+                // Only decode the valid UTF-8 prefix; keep any trailing
+                // partial multi-byte sequence buffered for the next window.
+                let valid_len = match std::str::from_utf8(&pending_bytes) {
+                    Ok(_) => pending_bytes.len(),
+                    Err(e) => e.valid_up_to(),
+                };
+                buffer.push_str(&String::from_utf8_lossy(&pending_bytes[..valid_len]));
+                pending_bytes.drain(..valid_len);
+
+                // Flush paragraph-aligned chunks once there's enough
+                // buffered text (plus overlap) to emit one.
+                while token_counter.count_tokens(&buffer) > target_tokens_max {
+                    let split_at = paragraph_split_point(&buffer, target_tokens_max, token_counter.as_ref())
+                        .unwrap_or_else(|| buffer.len());
+
+                    let chunk_text: String = buffer.drain(..split_at).collect();
+                    let chunk_len = chunk_text.chars().count();
+                    let chunk_end = buffer_start + chunk_len;
+
+                    yield Chunk::new(
+                        doc_id.clone(),
+                        chunk_text.clone(),
+                        source.clone(),
+                        (buffer_start, chunk_end),
+                    );
+
+                    let overlap = overlap_suffix(&chunk_text, overlap_tokens, token_counter.as_ref());
+                    buffer_start = chunk_end - overlap.chars().count();
+                    buffer = overlap + &buffer;
+                }
+            }
         }
-        
-        words[words.len().saturating_sub(target_words)..]
-            .join(" ")
     }
 }
 
+/// Find the last paragraph break (`"\n\n"`) whose prefix of `buffer` still
+/// fits in `target_tokens`, so a flushed streaming chunk stays on-budget
+/// without splitting mid-paragraph. When even the first paragraph is over
+/// budget, its break is returned anyway; `None` only if there is no break.
+fn paragraph_split_point(buffer: &str, target_tokens: usize, counter: &dyn TokenCounter) -> Option<usize> {
+    let mut best = None;
+    let mut search_from = 0;
+
+    while let Some(rel) = buffer[search_from..].find("\n\n") {
+        let pos = search_from + rel + 2;
+        if best.is_some() && counter.count_tokens(&buffer[..pos]) > target_tokens {
+            break;
+        }
+        best = Some(pos);
+        search_from = pos;
+    }
+
+    best
+}
+
+/// Trailing `target_tokens` tokens of `text` to carry over as overlap into
+/// the next streamed chunk, or no overlap at all if `text` doesn't even
+/// fill the overlap budget on its own.
+fn overlap_suffix(text: &str, target_tokens: usize, counter: &dyn TokenCounter) -> String {
+    if counter.count_tokens(text) <= target_tokens {
+        return String::new();
+    }
+
+    counter.tail(text, target_tokens)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
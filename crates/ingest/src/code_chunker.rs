@@ -0,0 +1,43 @@
+use tree_sitter::{Language, Parser};
+
+/// Programming languages `Chunker` can parse with tree-sitter for
+/// syntax-aware chunking, carving chunks along top-level item boundaries
+/// (functions, classes, impl blocks, ...) instead of blank lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CodeLanguage {
+    Rust,
+    Python,
+    JavaScript,
+}
+
+impl CodeLanguage {
+    /// Best-effort detection from a source file's extension, so callers
+    /// don't have to set `ChunkerConfig::language` explicitly for every
+    /// file.
+    pub fn from_extension(source: &str) -> Option<Self> {
+        let ext = std::path::Path::new(source).extension()?.to_str()?;
+        match ext {
+            "rs" => Some(Self::Rust),
+            "py" => Some(Self::Python),
+            "js" | "jsx" | "mjs" => Some(Self::JavaScript),
+            _ => None,
+        }
+    }
+
+    fn grammar(self) -> Language {
+        match self {
+            Self::Rust => tree_sitter_rust::language(),
+            Self::Python => tree_sitter_python::language(),
+            Self::JavaScript => tree_sitter_javascript::language(),
+        }
+    }
+}
+
+/// Parse `text` as `language`, returning `None` if the grammar can't be
+/// loaded or the source fails to parse at all (callers fall back to
+/// prose-style chunking in that case).
+pub fn parse(text: &str, language: CodeLanguage) -> Option<tree_sitter::Tree> {
+    let mut parser = Parser::new();
+    parser.set_language(&language.grammar()).ok()?;
+    parser.parse(text, None)
+}
@@ -13,13 +13,23 @@ mod tests {
     }
 }
 
+pub mod checkpoint;
 pub mod chunk;
 pub mod chunker;
+pub mod code_chunker;
+pub mod manifest;
 pub mod reader;
+pub mod source_store;
+pub mod token_counter;
 
+pub use checkpoint::{Checkpoint, ChunkState};
 pub use chunk::Chunk;
 pub use chunker::{Chunker, ChunkerConfig};
-pub use reader::FileReader;
+pub use code_chunker::CodeLanguage;
+pub use token_counter::{BpeTokenCounter, HeuristicTokenCounter, TokenCounter};
+pub use manifest::{diff_chunks, ingest_directory_incremental, watch_and_diff, ChunkDiff, IngestManifest};
+pub use reader::{DocumentLoader, FileReader, LoadedDoc};
+pub use source_store::{LocalFileStore, S3Store, S3StoreConfig, SourceStore};
 
 use anyhow::Result;
 use sha2::{Digest, Sha256};
@@ -33,30 +43,81 @@ pub fn generate_doc_id(path: &str) -> String {
     hex::encode(&result[..16])
 }
 
+/// A `LoadedDoc`'s doc_id groups by its *file*, not its sub-document: a
+/// loader that splits one file into several docs (PDF pages, JSONL records)
+/// appends a `#`-prefixed suffix to `source` for each one, so stripping that
+/// suffix recovers the shared file path every sub-document should group
+/// under.
+fn doc_id_for_source(source: &str) -> String {
+    let file_path = source.split('#').next().unwrap_or(source);
+    generate_doc_id(file_path)
+}
+
+/// The `ChunkerConfig` used by every ingest entry point below: real
+/// `cl100k_base` BPE token counts instead of the word-count heuristic, so
+/// chunk sizes reflect what the embedding/LLM backends actually see. Falls
+/// back to the heuristic counter if the BPE encoding can't be loaded (e.g.
+/// no network access to fetch its rank file), so ingest still works offline
+/// instead of failing outright.
+fn default_chunker_config() -> ChunkerConfig {
+    match BpeTokenCounter::cl100k_base() {
+        Ok(counter) => ChunkerConfig {
+            token_counter: std::sync::Arc::new(counter),
+            ..ChunkerConfig::default()
+        },
+        Err(e) => {
+            eprintln!("Falling back to heuristic token counting: {}", e);
+            ChunkerConfig::default()
+        }
+    }
+}
+
 /// Main ingestion pipeline
 pub async fn ingest_file(file_path: &Path) -> Result<Vec<Chunk>> {
-    let content = FileReader::read_file(file_path).await?;
-    let path_str = file_path.to_string_lossy().to_string();
-    let doc_id = generate_doc_id(&path_str);
-    
-    let chunker = Chunker::new(ChunkerConfig::default());
-    let chunks = chunker.chunk_text(&doc_id, &content, &path_str);
-    
-    Ok(chunks)
+    let docs = FileReader::read_file(file_path).await?;
+    let chunker = Chunker::new(default_chunker_config());
+
+    let mut all_chunks = Vec::new();
+    for doc in docs {
+        let doc_id = doc_id_for_source(&doc.source);
+        let chunks = chunker.chunk_text(&doc_id, &doc.content, &doc.source);
+        all_chunks.extend(chunks);
+    }
+
+    Ok(all_chunks)
 }
 
 /// Ingest entire directory
 pub async fn ingest_directory(dir_path: &Path) -> Result<Vec<Chunk>> {
-    let files = FileReader::read_directory(dir_path).await?;
-    let chunker = Chunker::new(ChunkerConfig::default());
-    
+    let docs = FileReader::read_directory(dir_path).await?;
+    let chunker = Chunker::new(default_chunker_config());
+
     let mut all_chunks = Vec::new();
-    
-    for (path, content) in files {
-        let doc_id = generate_doc_id(&path);
-        let chunks = chunker.chunk_text(&doc_id, &content, &path);
+    for doc in docs {
+        let doc_id = doc_id_for_source(&doc.source);
+        let chunks = chunker.chunk_text(&doc_id, &doc.content, &doc.source);
         all_chunks.extend(chunks);
     }
-    
+
+    Ok(all_chunks)
+}
+
+/// Ingest every object in a `SourceStore` (local directory, S3-compatible
+/// bucket, ...) as it's listed, rather than requiring the corpus to already
+/// be on local disk. Each object's body is read in full into a `String` by
+/// `SourceStore::read` before it is chunked; bodies are not streamed.
+pub async fn ingest_source(store: &dyn SourceStore) -> Result<Vec<Chunk>> {
+    let keys = store.list().await?;
+    let chunker = Chunker::new(default_chunker_config());
+
+    let mut all_chunks = Vec::new();
+
+    for key in keys {
+        let content = store.read(&key).await?;
+        let doc_id = generate_doc_id(&key);
+        let chunks = chunker.chunk_text(&doc_id, &content, &key);
+        all_chunks.extend(chunks);
+    }
+
     Ok(all_chunks)
 }
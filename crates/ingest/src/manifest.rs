@@ -0,0 +1,118 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::chunk::Chunk;
+
+/// Persisted record of which chunk IDs were present after the last ingest,
+/// so a later run can diff against it instead of reprocessing everything.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct IngestManifest {
+    pub chunk_ids: HashSet<String>,
+}
+
+impl IngestManifest {
+    /// Load a manifest from disk, or an empty one if it doesn't exist yet
+    /// (e.g. the very first ingest of a corpus).
+    pub async fn load(path: &Path) -> Result<Self> {
+        match tokio::fs::read_to_string(path).await {
+            Ok(raw) => serde_json::from_str(&raw).context("Failed to parse ingest manifest"),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e).context(format!("Failed to read manifest: {:?}", path)),
+        }
+    }
+
+    pub async fn save(&self, path: &Path) -> Result<()> {
+        let raw = serde_json::to_string_pretty(self)?;
+        tokio::fs::write(path, raw)
+            .await
+            .context(format!("Failed to write manifest: {:?}", path))
+    }
+}
+
+/// Result of diffing a freshly-chunked corpus against the previous
+/// manifest: chunks to embed/index, chunk IDs whose vectors/graph nodes
+/// should be deleted, and chunk IDs that are unchanged and can be skipped.
+#[derive(Debug, Default)]
+pub struct ChunkDiff {
+    pub added: Vec<Chunk>,
+    pub removed: Vec<String>,
+    pub unchanged: Vec<String>,
+}
+
+/// Diff a freshly-chunked corpus against the previous manifest's chunk IDs.
+/// Because `Chunk::generate_chunk_id` is a deterministic hash of
+/// `(doc_id, text, offset)`, an unchanged chunk always re-hashes to the
+/// same ID, so this is a plain set comparison.
+pub fn diff_chunks(previous: &IngestManifest, current: &[Chunk]) -> ChunkDiff {
+    let current_ids: HashSet<&str> = current.iter().map(|c| c.chunk_id.as_str()).collect();
+
+    let added = current
+        .iter()
+        .filter(|c| !previous.chunk_ids.contains(&c.chunk_id))
+        .cloned()
+        .collect();
+
+    let unchanged = current
+        .iter()
+        .filter(|c| previous.chunk_ids.contains(&c.chunk_id))
+        .map(|c| c.chunk_id.clone())
+        .collect();
+
+    let removed = previous
+        .chunk_ids
+        .iter()
+        .filter(|id| !current_ids.contains(id.as_str()))
+        .cloned()
+        .collect();
+
+    ChunkDiff {
+        added,
+        removed,
+        unchanged,
+    }
+}
+
+/// Re-chunk `dir_path`, diff against the manifest at `manifest_path`, and
+/// persist the new chunk-ID set so the next run can diff against this one.
+pub async fn ingest_directory_incremental(
+    dir_path: &Path,
+    manifest_path: &Path,
+) -> Result<ChunkDiff> {
+    let previous = IngestManifest::load(manifest_path).await?;
+    let current_chunks = crate::ingest_directory(dir_path).await?;
+
+    let diff = diff_chunks(&previous, &current_chunks);
+
+    let new_manifest = IngestManifest {
+        chunk_ids: current_chunks.iter().map(|c| c.chunk_id.clone()).collect(),
+    };
+    new_manifest.save(manifest_path).await?;
+
+    Ok(diff)
+}
+
+/// Poll `dir_path` every `poll_interval` and call `on_diff` whenever an
+/// incremental ingest finds any added or removed chunks, so a long-running
+/// index stays current without a full rebuild on every run. Runs until the
+/// process is stopped; callers that need a bounded run should wrap this in
+/// `tokio::time::timeout` or spawn it as a cancellable task.
+pub async fn watch_and_diff(
+    dir_path: &Path,
+    manifest_path: &Path,
+    poll_interval: Duration,
+    mut on_diff: impl FnMut(ChunkDiff),
+) -> Result<()> {
+    let mut interval = tokio::time::interval(poll_interval);
+
+    loop {
+        interval.tick().await;
+        let diff = ingest_directory_incremental(dir_path, manifest_path).await?;
+
+        if !diff.added.is_empty() || !diff.removed.is_empty() {
+            on_diff(diff);
+        }
+    }
+}
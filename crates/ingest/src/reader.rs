@@ -1,46 +1,269 @@
 use anyhow::{Context, Result};
-use std::path::Path;
+use async_trait::async_trait;
+use std::collections::{HashMap, VecDeque};
+use std::path::{Path, PathBuf};
 use tokio::fs;
 
-pub struct FileReader;
+/// One ingestible document produced by a `DocumentLoader`. A single file can
+/// expand into several of these (one per JSONL line/CSV row, one per PDF
+/// page), so `source` carries enough to tell the sub-documents of one file
+/// apart, and `metadata` carries whatever provenance the loader could
+/// recover (page number, record offset, ...) instead of losing it at the
+/// file boundary.
+#[derive(Debug, Clone)]
+pub struct LoadedDoc {
+    pub source: String,
+    pub content: String,
+    pub metadata: HashMap<String, String>,
+}
 
-impl FileReader {
-    pub async fn read_file(path: &Path) -> Result<String> {
-        let extension = path
-            .extension()
-            .and_then(|e| e.to_str())
-            .unwrap_or("");
-
-        match extension {
-            "txt" | "md" => {
-                let content = fs::read_to_string(path)
-                    .await
-                    .context(format!("Failed to read file: {:?}", path))?;
-                Ok(content)
+/// A pluggable reader for one file format. `FileReader` dispatches to
+/// whichever registered loader's `can_handle` matches a file's extension,
+/// so adding a new format doesn't mean growing a single giant match.
+#[async_trait]
+pub trait DocumentLoader: Send + Sync {
+    /// Whether this loader handles files with the given extension
+    /// (lowercased, no leading dot).
+    fn can_handle(&self, ext: &str) -> bool;
+
+    /// Load `path`, already known to satisfy `can_handle`.
+    async fn load(&self, path: &Path) -> Result<Vec<LoadedDoc>>;
+}
+
+/// The registered loaders, tried in order. `FileReader::read_file`/
+/// `read_directory` pick the first one whose `can_handle` returns true.
+fn loaders() -> Vec<Box<dyn DocumentLoader>> {
+    vec![
+        Box::new(TextLoader),
+        Box::new(HtmlLoader),
+        Box::new(PdfLoader),
+        Box::new(JsonlCsvLoader),
+    ]
+}
+
+/// Whether any registered loader can handle files with this extension
+/// (lowercased, no leading dot). Exposed so other ingest-side filtering
+/// (e.g. `SourceStore` listings, upload validation) stays in sync with
+/// `FileReader` instead of hardcoding its own extension list.
+pub fn supported_extension(ext: &str) -> bool {
+    loaders().iter().any(|loader| loader.can_handle(ext))
+}
+
+/// Plain text and Markdown: the whole file is one document, verbatim.
+struct TextLoader;
+
+#[async_trait]
+impl DocumentLoader for TextLoader {
+    fn can_handle(&self, ext: &str) -> bool {
+        matches!(ext, "txt" | "md")
+    }
+
+    async fn load(&self, path: &Path) -> Result<Vec<LoadedDoc>> {
+        let content = fs::read_to_string(path)
+            .await
+            .context(format!("Failed to read file: {:?}", path))?;
+
+        Ok(vec![LoadedDoc {
+            source: path.to_string_lossy().to_string(),
+            content,
+            metadata: HashMap::new(),
+        }])
+    }
+}
+
+/// HTML: strip `<script>`/`<style>` blocks and remaining tags down to their
+/// text content.
+struct HtmlLoader;
+
+#[async_trait]
+impl DocumentLoader for HtmlLoader {
+    fn can_handle(&self, ext: &str) -> bool {
+        matches!(ext, "html" | "htm")
+    }
+
+    async fn load(&self, path: &Path) -> Result<Vec<LoadedDoc>> {
+        let raw = fs::read_to_string(path)
+            .await
+            .context(format!("Failed to read file: {:?}", path))?;
+
+        Ok(vec![LoadedDoc {
+            source: path.to_string_lossy().to_string(),
+            content: strip_html(&raw),
+            metadata: HashMap::new(),
+        }])
+    }
+}
+
+/// Strip script/style blocks (their contents aren't prose), then strip
+/// remaining tags and collapse the whitespace they leave behind.
+fn strip_html(html: &str) -> String {
+    let without_scripts = strip_tag_blocks(html, "script");
+    let without_style = strip_tag_blocks(&without_scripts, "style");
+
+    let mut text = String::with_capacity(without_style.len());
+    let mut in_tag = false;
+    for c in without_style.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Remove every `<tag>...</tag>` block (case-insensitive) from `html`.
+fn strip_tag_blocks(html: &str, tag: &str) -> String {
+    let open = format!("<{}", tag);
+    let close = format!("</{}>", tag);
+    let lower = html.to_lowercase();
+
+    let mut result = String::with_capacity(html.len());
+    let mut pos = 0;
+    while let Some(rel_start) = lower[pos..].find(&open) {
+        let start = pos + rel_start;
+        result.push_str(&html[pos..start]);
+        match lower[start..].find(&close) {
+            Some(rel_end) => pos = start + rel_end + close.len(),
+            None => {
+                pos = html.len();
+                break;
             }
-            _ => anyhow::bail!("Unsupported file format: {}", extension),
         }
     }
+    result.push_str(&html[pos..]);
+    result
+}
+
+/// PDF: extract text per page, so a large document's provenance stays at
+/// page granularity instead of collapsing into one undifferentiated blob.
+struct PdfLoader;
+
+#[async_trait]
+impl DocumentLoader for PdfLoader {
+    fn can_handle(&self, ext: &str) -> bool {
+        ext == "pdf"
+    }
+
+    async fn load(&self, path: &Path) -> Result<Vec<LoadedDoc>> {
+        let source = path.to_string_lossy().to_string();
+        let owned_path = path.to_path_buf();
+
+        // Parsing is CPU-bound and synchronous, so it runs on the blocking
+        // pool rather than tying up an async worker thread.
+        let pages = tokio::task::spawn_blocking(move || pdf_extract::extract_text_by_pages(&owned_path))
+            .await
+            .context("PDF extraction task panicked")?
+            .with_context(|| format!("Failed to extract text from PDF: {}", source))?;
+
+        Ok(pages
+            .into_iter()
+            .enumerate()
+            .map(|(i, content)| {
+                let page = i + 1;
+                let mut metadata = HashMap::new();
+                metadata.insert("page".to_string(), page.to_string());
+                LoadedDoc {
+                    source: format!("{}#page={}", source, page),
+                    content,
+                    metadata,
+                }
+            })
+            .collect())
+    }
+}
+
+/// JSONL and CSV: one record per line/row becomes its own document, so a
+/// dataset file ingests as many small documents instead of one chunk-sized
+/// blob that mixes unrelated records together.
+struct JsonlCsvLoader;
+
+#[async_trait]
+impl DocumentLoader for JsonlCsvLoader {
+    fn can_handle(&self, ext: &str) -> bool {
+        matches!(ext, "jsonl" | "csv")
+    }
+
+    async fn load(&self, path: &Path) -> Result<Vec<LoadedDoc>> {
+        let raw = fs::read_to_string(path)
+            .await
+            .context(format!("Failed to read file: {:?}", path))?;
+        let source = path.to_string_lossy().to_string();
 
-    pub async fn read_directory(dir: &Path) -> Result<Vec<(String, String)>> {
-        let mut files = Vec::new();
-        
-        let mut entries = fs::read_dir(dir).await?;
-        
-        while let Some(entry) = entries.next_entry().await? {
-            let path = entry.path();
-            
-            if path.is_file() {
-                if let Some(ext) = path.extension() {
-                    if ext == "txt" || ext == "md" {
-                        let content = Self::read_file(&path).await?;
-                        let path_str = path.to_string_lossy().to_string();
-                        files.push((path_str, content));
-                    }
+        Ok(raw
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(offset, line)| {
+                let mut metadata = HashMap::new();
+                metadata.insert("record_offset".to_string(), offset.to_string());
+                LoadedDoc {
+                    source: format!("{}#{}", source, offset),
+                    content: line.to_string(),
+                    metadata,
+                }
+            })
+            .collect())
+    }
+}
+
+pub struct FileReader;
+
+impl FileReader {
+    /// Load a single file into one or more `LoadedDoc`s, dispatching to the
+    /// registered `DocumentLoader` whose `can_handle` matches the file's
+    /// extension.
+    pub async fn read_file(path: &Path) -> Result<Vec<LoadedDoc>> {
+        let extension = Self::extension_of(path);
+
+        let loader = loaders()
+            .into_iter()
+            .find(|loader| loader.can_handle(&extension))
+            .ok_or_else(|| anyhow::anyhow!("Unsupported file format: {}", extension))?;
+
+        loader.load(path).await
+    }
+
+    /// Recursively walk `dir`, loading every file a registered loader can
+    /// handle. Files with no matching loader (or that fail to load) are
+    /// skipped with a warning instead of aborting the whole scan.
+    pub async fn read_directory(dir: &Path) -> Result<Vec<LoadedDoc>> {
+        let mut docs = Vec::new();
+        let mut pending: VecDeque<PathBuf> = VecDeque::new();
+        pending.push_back(dir.to_path_buf());
+
+        while let Some(current) = pending.pop_front() {
+            let mut entries = fs::read_dir(&current).await?;
+
+            while let Some(entry) = entries.next_entry().await? {
+                let path = entry.path();
+
+                if path.is_dir() {
+                    pending.push_back(path);
+                    continue;
+                }
+
+                if !supported_extension(&Self::extension_of(&path)) {
+                    eprintln!("Skipping unsupported file: {:?}", path);
+                    continue;
+                }
+
+                match Self::read_file(&path).await {
+                    Ok(mut loaded) => docs.append(&mut loaded),
+                    Err(e) => eprintln!("Skipping file {:?}: {}", path, e),
                 }
             }
         }
-        
-        Ok(files)
+
+        Ok(docs)
+    }
+
+    fn extension_of(path: &Path) -> String {
+        path.extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or("")
+            .to_lowercase()
     }
-}
\ No newline at end of file
+}
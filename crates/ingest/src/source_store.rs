@@ -0,0 +1,273 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::fs;
+
+use crate::reader::FileReader;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Abstraction over where ingestible documents live, so `ingest_source`
+/// can pull from a local filesystem or an S3-compatible object store
+/// without the ingestion pipeline caring which.
+#[async_trait]
+pub trait SourceStore: Send + Sync {
+    /// List the full URIs/keys of every ingestible object in this store.
+    async fn list(&self) -> Result<Vec<String>>;
+
+    /// Read the full text content of a single object by its key.
+    async fn read(&self, key: &str) -> Result<String>;
+}
+
+/// `SourceStore` backed by a local directory, filtered to whatever
+/// extensions `FileReader`'s registered `DocumentLoader`s support.
+pub struct LocalFileStore {
+    root: PathBuf,
+}
+
+impl LocalFileStore {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+}
+
+#[async_trait]
+impl SourceStore for LocalFileStore {
+    async fn list(&self) -> Result<Vec<String>> {
+        let mut keys = Vec::new();
+        let mut entries = fs::read_dir(&self.root).await?;
+
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            if path.is_file() {
+                if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
+                    if crate::reader::supported_extension(&ext.to_lowercase()) {
+                        keys.push(path.to_string_lossy().to_string());
+                    }
+                }
+            }
+        }
+
+        Ok(keys)
+    }
+
+    /// A key can expand into several `LoadedDoc`s (e.g. one per JSONL line);
+    /// join their content back into the single string this trait's callers
+    /// expect, since `SourceStore` addresses whole objects by key, not
+    /// sub-documents.
+    async fn read(&self, key: &str) -> Result<String> {
+        let docs = FileReader::read_file(Path::new(key)).await?;
+        Ok(docs
+            .into_iter()
+            .map(|doc| doc.content)
+            .collect::<Vec<_>>()
+            .join("\n\n"))
+    }
+}
+
+/// Connection details for an S3-compatible bucket (AWS S3, MinIO, R2, ...).
+#[derive(Clone)]
+pub struct S3StoreConfig {
+    pub endpoint: String,
+    pub region: String,
+    pub bucket: String,
+    pub access_key: String,
+    pub secret_key: String,
+    pub prefix: String,
+}
+
+/// `SourceStore` backed by an S3-compatible bucket. Objects are addressed
+/// by their full `s3://bucket/key` URI so `generate_doc_id` stays stable
+/// across ingests even if the local filesystem layout changes.
+pub struct S3Store {
+    config: S3StoreConfig,
+    client: reqwest::Client,
+}
+
+impl S3Store {
+    pub fn new(config: S3StoreConfig) -> Self {
+        Self {
+            config,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Stable URI used as the document ID source for an object key.
+    fn object_uri(&self, key: &str) -> String {
+        format!("s3://{}/{}", self.config.bucket, key)
+    }
+
+    fn host(&self) -> Result<String> {
+        let without_scheme = self
+            .config
+            .endpoint
+            .split("://")
+            .nth(1)
+            .context("S3 endpoint must include a scheme, e.g. https://s3.example.com")?;
+        Ok(without_scheme.trim_end_matches('/').to_string())
+    }
+
+    /// Sign and send a path-style `GET {bucket}/{path}?{query}` request
+    /// against the configured endpoint using AWS Signature Version 4.
+    async fn signed_get(&self, path: &str, query: &str) -> Result<reqwest::Response> {
+        let host = self.host()?;
+        let canonical_uri = format!("/{}/{}", self.config.bucket, path);
+        let url = format!("{}{}?{}", self.config.endpoint.trim_end_matches('/'), canonical_uri, query)
+            .trim_end_matches('?')
+            .to_string();
+
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+        let (date_stamp, amz_date) = format_amz_timestamps(now);
+        let payload_hash = sha256_hex(b"");
+
+        let canonical_headers = format!(
+            "host:{}\nx-amz-content-sha256:{}\nx-amz-date:{}\n",
+            host, payload_hash, amz_date
+        );
+        let signed_headers = "host;x-amz-content-sha256;x-amz-date";
+
+        let canonical_request = format!(
+            "GET\n{}\n{}\n{}\n{}\n{}",
+            canonical_uri, query, canonical_headers, signed_headers, payload_hash
+        );
+
+        let credential_scope = format!("{}/{}/s3/aws4_request", date_stamp, self.config.region);
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+            amz_date,
+            credential_scope,
+            sha256_hex(canonical_request.as_bytes())
+        );
+
+        let signing_key = derive_signing_key(&self.config.secret_key, &date_stamp, &self.config.region, "s3");
+        let signature = hex::encode(hmac_sha256(&signing_key, string_to_sign.as_bytes()));
+
+        let authorization = format!(
+            "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+            self.config.access_key, credential_scope, signed_headers, signature
+        );
+
+        let response = self
+            .client
+            .get(&url)
+            .header("x-amz-date", amz_date)
+            .header("x-amz-content-sha256", payload_hash)
+            .header("Authorization", authorization)
+            .send()
+            .await
+            .context("Failed to send request to object store")?;
+
+        if !response.status().is_success() {
+            anyhow::bail!("Object store request failed: {}", response.status());
+        }
+
+        Ok(response)
+    }
+}
+
+#[async_trait]
+impl SourceStore for S3Store {
+    async fn list(&self) -> Result<Vec<String>> {
+        let query = format!(
+            "list-type=2&prefix={}",
+            urlencoding_encode(&self.config.prefix)
+        );
+        let response = self.signed_get("", &query).await?;
+        let body = response.text().await?;
+
+        // Minimal ListObjectsV2 XML parse: pull out every <Key>...</Key>.
+        let mut keys = Vec::new();
+        let mut rest = body.as_str();
+        while let Some(start) = rest.find("<Key>") {
+            let after_start = &rest[start + "<Key>".len()..];
+            let end = after_start
+                .find("</Key>")
+                .context("Malformed ListObjectsV2 response: unterminated <Key>")?;
+            keys.push(self.object_uri(&after_start[..end]));
+            rest = &after_start[end + "</Key>".len()..];
+        }
+
+        Ok(keys)
+    }
+
+    async fn read(&self, key: &str) -> Result<String> {
+        let object_key = key
+            .strip_prefix(&format!("s3://{}/", self.config.bucket))
+            .unwrap_or(key);
+        let response = self.signed_get(object_key, "").await?;
+        response
+            .text()
+            .await
+            .context("Failed to read object store response body")
+    }
+}
+
+fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn sha256_hex(data: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(data);
+    hex::encode(hasher.finalize())
+}
+
+fn derive_signing_key(secret_key: &str, date_stamp: &str, region: &str, service: &str) -> Vec<u8> {
+    let k_date = hmac_sha256(format!("AWS4{}", secret_key).as_bytes(), date_stamp.as_bytes());
+    let k_region = hmac_sha256(&k_date, region.as_bytes());
+    let k_service = hmac_sha256(&k_region, service.as_bytes());
+    hmac_sha256(&k_service, b"aws4_request")
+}
+
+/// Render a Unix timestamp as the `(YYYYMMDD, YYYYMMDDTHHMMSSZ)` pair SigV4
+/// needs, without pulling in a full date/time crate.
+fn format_amz_timestamps(unix_seconds: u64) -> (String, String) {
+    let days = unix_seconds / 86_400;
+    let secs_of_day = unix_seconds % 86_400;
+    let (year, month, day) = civil_from_days(days as i64);
+
+    let date_stamp = format!("{:04}{:02}{:02}", year, month, day);
+    let amz_date = format!(
+        "{}T{:02}{:02}{:02}Z",
+        date_stamp,
+        secs_of_day / 3600,
+        (secs_of_day % 3600) / 60,
+        secs_of_day % 60
+    );
+
+    (date_stamp, amz_date)
+}
+
+/// Howard Hinnant's `civil_from_days` algorithm: days since the Unix epoch
+/// to a proleptic-Gregorian `(year, month, day)`.
+fn civil_from_days(z: i64) -> (i64, u32, u32) {
+    let z = z + 719_468;
+    let era = if z >= 0 { z } else { z - 146_096 } / 146_097;
+    let doe = (z - era * 146_097) as u64;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let y = yoe as i64 + era * 400;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = if m <= 2 { y + 1 } else { y };
+    (year, m, d)
+}
+
+fn urlencoding_encode(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                out.push(byte as char)
+            }
+            _ => out.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    out
+}
@@ -0,0 +1,66 @@
+/// Counts tokens in text and slices a token-aligned tail from it, so
+/// `Chunker`'s sizing reflects how a tokenizer actually sees the text
+/// rather than a word-count approximation.
+pub trait TokenCounter: Send + Sync {
+    /// Number of tokens in `text`.
+    fn count_tokens(&self, text: &str) -> usize;
+
+    /// The trailing `n_tokens` tokens of `text`, used to build overlap
+    /// between consecutive chunks.
+    fn tail(&self, text: &str, n_tokens: usize) -> String;
+}
+
+/// Fallback counter approximating tokens as `word_count * 1.3`, the
+/// original heuristic. Used when no real tokenizer is configured; drifts
+/// from true token counts for code, CJK text, or punctuation-heavy input.
+pub struct HeuristicTokenCounter;
+
+impl TokenCounter for HeuristicTokenCounter {
+    fn count_tokens(&self, text: &str) -> usize {
+        let word_count = text.split_whitespace().count();
+        (word_count as f64 * 1.3) as usize
+    }
+
+    fn tail(&self, text: &str, n_tokens: usize) -> String {
+        let words: Vec<&str> = text.split_whitespace().collect();
+        let target_words = (n_tokens as f64 / 1.3) as usize;
+
+        if words.len() <= target_words {
+            return text.to_string();
+        }
+
+        words[words.len().saturating_sub(target_words)..].join(" ")
+    }
+}
+
+/// Real byte-pair-encoding counter backed by a tiktoken-style encoding
+/// (e.g. `cl100k_base`, the encoding GPT-3.5/4 and `text-embedding-3-*`
+/// use), so chunk sizes reflect actual model token counts rather than an
+/// approximation.
+pub struct BpeTokenCounter {
+    encoding: tiktoken_rs::CoreBPE,
+}
+
+impl BpeTokenCounter {
+    pub fn cl100k_base() -> anyhow::Result<Self> {
+        Ok(Self {
+            encoding: tiktoken_rs::cl100k_base()?,
+        })
+    }
+}
+
+impl TokenCounter for BpeTokenCounter {
+    fn count_tokens(&self, text: &str) -> usize {
+        self.encoding.encode_with_special_tokens(text).len()
+    }
+
+    fn tail(&self, text: &str, n_tokens: usize) -> String {
+        let tokens = self.encoding.encode_with_special_tokens(text);
+        if tokens.len() <= n_tokens {
+            return text.to_string();
+        }
+
+        let tail_tokens = tokens[tokens.len() - n_tokens..].to_vec();
+        self.encoding.decode(tail_tokens).unwrap_or_default()
+    }
+}
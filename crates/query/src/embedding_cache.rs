@@ -0,0 +1,101 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+/// Default sidecar file persisting community-summary embeddings across
+/// process restarts, so `GlobalSearchEngine` only pays the embedding cost
+/// for a given summary once no matter how many queries run against it.
+pub const DEFAULT_EMBEDDING_CACHE_PATH: &str = "data/communities/.embcache/embeddings.json";
+
+/// Content-addressed embedding cache: entries are keyed by the SHA-256
+/// digest of the embedding model's id and the source text (see
+/// `digest_for`), so an unchanged summary always hits the cache and an
+/// edited summary (different digest) always misses and gets re-embedded.
+pub struct PersistentEmbeddingCache {
+    path: PathBuf,
+    entries: Mutex<HashMap<String, Vec<f32>>>,
+}
+
+impl PersistentEmbeddingCache {
+    /// Load the cache from `path`, starting empty if the file doesn't
+    /// exist yet (e.g. the first run against a fresh corpus).
+    pub fn load(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let entries = match std::fs::read_to_string(&path) {
+            Ok(raw) => serde_json::from_str(&raw).context("Failed to parse embedding cache")?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => HashMap::new(),
+            Err(e) => return Err(e).context(format!("Failed to read embedding cache: {:?}", path)),
+        };
+
+        Ok(Self {
+            path,
+            entries: Mutex::new(entries),
+        })
+    }
+
+    /// An empty cache backed by `path`, for callers that want to fall back
+    /// gracefully if `load` fails (e.g. a corrupt sidecar file).
+    pub fn empty(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// SHA-256 hex digest of `model_id` and `text`, used as the cache key.
+    /// Keying on `model_id` as well as the text means switching embedding
+    /// providers (which changes embedding geometry and dimensionality)
+    /// naturally misses the cache instead of silently mixing incompatible
+    /// vectors into the same cosine comparison.
+    pub fn digest_for(model_id: &str, text: &str) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(model_id.as_bytes());
+        hasher.update(b"\0");
+        hasher.update(text.as_bytes());
+        hex::encode(hasher.finalize())
+    }
+
+    /// Split `digests` into already-cached vectors and the digests that
+    /// still need to be embedded.
+    pub fn embeddings_for_digests(
+        &self,
+        digests: &[String],
+    ) -> (HashMap<String, Vec<f32>>, Vec<String>) {
+        let entries = self.entries.lock().unwrap();
+        let mut hits = HashMap::new();
+        let mut misses = Vec::new();
+
+        for digest in digests {
+            match entries.get(digest) {
+                Some(vector) => {
+                    hits.insert(digest.clone(), vector.clone());
+                }
+                None => misses.push(digest.clone()),
+            }
+        }
+
+        (hits, misses)
+    }
+
+    /// Record a freshly-computed embedding and persist the cache to disk.
+    pub fn insert(&self, digest: String, vector: Vec<f32>) -> Result<()> {
+        {
+            let mut entries = self.entries.lock().unwrap();
+            entries.insert(digest, vector);
+        }
+        self.save()
+    }
+
+    fn save(&self) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let entries = self.entries.lock().unwrap();
+        let raw = serde_json::to_string_pretty(&*entries)?;
+        std::fs::write(&self.path, raw)
+            .context(format!("Failed to write embedding cache: {:?}", self.path))
+    }
+}
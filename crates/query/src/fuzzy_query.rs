@@ -0,0 +1,184 @@
+use serde::{Deserialize, Serialize};
+
+/// A boolean query tree over whitespace-separated search terms. `And`
+/// requires every child to match, `Or` requires at least one. `Query` is a
+/// leaf: a single term, optionally allowed to match a name's word as a
+/// prefix rather than requiring a full fuzzy match.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Operation {
+    And(Vec<Operation>),
+    Or(Vec<Operation>),
+    Query { term: String, prefix: bool },
+}
+
+/// Which entity matched, the query term that matched one of its name's
+/// words, and the edit distance (0 for an exact or prefix match).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FuzzyMatch {
+    pub entity_id: String,
+    pub entity_name: String,
+    pub term: String,
+    pub distance: usize,
+}
+
+/// Build the query tree for a raw search string: `And` across its
+/// whitespace-separated terms, with the last term additionally allowed to
+/// match as a prefix (combined with its fuzzy match via `Or`) so that
+/// partially-typed trailing terms still hit.
+pub fn build_query_tree(query: &str) -> Operation {
+    let terms: Vec<&str> = query.split_whitespace().collect();
+    let last_index = terms.len().saturating_sub(1);
+
+    let children: Vec<Operation> = terms
+        .iter()
+        .enumerate()
+        .map(|(i, term)| {
+            let exact = Operation::Query {
+                term: term.to_string(),
+                prefix: false,
+            };
+            if i == last_index {
+                let prefix = Operation::Query {
+                    term: term.to_string(),
+                    prefix: true,
+                };
+                Operation::Or(vec![exact, prefix])
+            } else {
+                exact
+            }
+        })
+        .collect();
+
+    Operation::And(children)
+}
+
+/// Edit-distance budget for a term, scaled by its length: short terms must
+/// match almost exactly, longer ones tolerate more typos.
+fn edit_distance_budget(term: &str) -> usize {
+    match term.chars().count() {
+        0..=4 => 0,
+        5..=8 => 1,
+        _ => 2,
+    }
+}
+
+/// Levenshtein distance between `a` and `b`, or `None` if it provably
+/// exceeds `budget`. Computed row-by-row over the classic O(m*n) DP table,
+/// bailing out as soon as a row's minimum exceeds the budget.
+fn levenshtein_within(a: &[char], b: &[char], budget: usize) -> Option<usize> {
+    let (m, n) = (a.len(), b.len());
+    if m.abs_diff(n) > budget {
+        return None;
+    }
+
+    let mut prev: Vec<usize> = (0..=n).collect();
+    for i in 1..=m {
+        let mut curr = vec![0usize; n + 1];
+        curr[0] = i;
+        let mut row_min = curr[0];
+
+        for j in 1..=n {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            curr[j] = (prev[j] + 1).min(curr[j - 1] + 1).min(prev[j - 1] + cost);
+            row_min = row_min.min(curr[j]);
+        }
+
+        if row_min > budget {
+            return None;
+        }
+        prev = curr;
+    }
+
+    let distance = prev[n];
+    (distance <= budget).then_some(distance)
+}
+
+/// Best (word, distance) match for `term` among `name_words`, or `None` if
+/// none of them is within the term's edit-distance budget (or, when
+/// `prefix` is set, a prefix match).
+fn match_term<'a>(term: &str, prefix: bool, name_words: &'a [String]) -> Option<(&'a str, usize)> {
+    let term: Vec<char> = term.to_lowercase().chars().collect();
+    let budget = edit_distance_budget(&term.iter().collect::<String>());
+
+    let mut best: Option<(&str, usize)> = None;
+    for word in name_words {
+        if prefix && word.starts_with(&term.iter().collect::<String>()) {
+            return Some((word, 0));
+        }
+
+        let word_chars: Vec<char> = word.chars().collect();
+        if let Some(distance) = levenshtein_within(&term, &word_chars, budget) {
+            if best.map_or(true, |(_, best_distance)| distance < best_distance) {
+                best = Some((word, distance));
+            }
+        }
+    }
+
+    best
+}
+
+/// Evaluate `op` against `name`'s lowercased words, returning the matched
+/// leaves (term, matched word, distance) if the whole tree is satisfied.
+pub fn evaluate(op: &Operation, name_words: &[String]) -> Option<Vec<(String, String, usize)>> {
+    match op {
+        Operation::Query { term, prefix } => {
+            match_term(term, *prefix, name_words)
+                .map(|(word, distance)| vec![(term.clone(), word.to_string(), distance)])
+        }
+        Operation::And(children) => {
+            let mut matches = Vec::new();
+            for child in children {
+                matches.extend(evaluate(child, name_words)?);
+            }
+            Some(matches)
+        }
+        Operation::Or(children) => children
+            .iter()
+            .filter_map(|child| evaluate(child, name_words))
+            .min_by_key(|matches| matches.iter().map(|(_, _, d)| *d).sum::<usize>()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn budget_scales_with_term_length() {
+        assert_eq!(edit_distance_budget("gra"), 0);
+        assert_eq!(edit_distance_budget("graph"), 1);
+        assert_eq!(edit_distance_budget("graphrag"), 1);
+        assert_eq!(edit_distance_budget("graphrags"), 2);
+    }
+
+    #[test]
+    fn levenshtein_matches_known_distances() {
+        let a: Vec<char> = "kitten".chars().collect();
+        let b: Vec<char> = "sitting".chars().collect();
+        assert_eq!(levenshtein_within(&a, &b, 3), Some(3));
+        assert_eq!(levenshtein_within(&a, &b, 2), None);
+    }
+
+    #[test]
+    fn single_typo_term_matches_misspelled_word() {
+        let tree = build_query_tree("graoh");
+        let name_words = vec!["graph".to_string(), "database".to_string()];
+        let matches = evaluate(&tree, &name_words);
+        assert!(matches.is_some());
+    }
+
+    #[test]
+    fn last_term_matches_as_prefix() {
+        let tree = build_query_tree("neo4j gra");
+        let name_words = vec!["neo4j".to_string(), "graph".to_string()];
+        let matches = evaluate(&tree, &name_words);
+        assert!(matches.is_some());
+    }
+
+    #[test]
+    fn missing_term_fails_the_and() {
+        let tree = build_query_tree("neo4j postgres");
+        let name_words = vec!["neo4j".to_string(), "graph".to_string()];
+        assert!(evaluate(&tree, &name_words).is_none());
+    }
+}
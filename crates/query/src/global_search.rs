@@ -1,10 +1,32 @@
 use anyhow::{Context, Result};
+use futures::stream::{self, StreamExt};
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::PathBuf;
 use tokio::fs;
 
+use crate::embedding_cache::{PersistentEmbeddingCache, DEFAULT_EMBEDDING_CACHE_PATH};
 use crate::llm::QueryLLM;
-use index::EmbeddingClient;
+use index::Embedder;
+
+/// How many summary embedding requests `embeddings_for_summaries` keeps in
+/// flight at once.
+const DEFAULT_EMBEDDING_CONCURRENCY: usize = 8;
+
+/// BM25 tuning constants, standard Okapi defaults.
+const BM25_K1: f32 = 1.2;
+const BM25_B: f32 = 0.75;
+
+/// Default Reciprocal Rank Fusion constant (Cormack et al.).
+const DEFAULT_RRF_K: f32 = 60.0;
+
+/// Default minimum-relevance floors, set at or below the lowest score each
+/// signal can produce (cosine similarity can be negative, BM25 never is).
+/// This preserves the historical behavior of always returning up to
+/// `top_k` communities regardless of how weak their score is.
+const DEFAULT_MIN_SCORE_VECTOR: f32 = f32::NEG_INFINITY;
+const DEFAULT_MIN_SCORE_TEXT: f32 = 0.0;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GlobalSearchResult {
@@ -16,82 +38,394 @@ pub struct GlobalSearchResult {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CommunityReference {
     pub community_id: usize,
+    /// Louvain hierarchy level `community_id` was assigned within -
+    /// `community_id` alone repeats across levels, so a client needs both
+    /// to uniquely identify a community.
+    pub level: usize,
     pub summary: String,
     pub relevance_score: f32,
+    /// Which signal(s) contributed to this community's ranking.
+    pub score_breakdown: ScoreBreakdown,
+}
+
+/// Per-community ranking detail, so `GlobalSearchTrace` can explain why a
+/// community was (or wasn't) surfaced by a hybrid search.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct ScoreBreakdown {
+    /// 1-based rank in the cosine-similarity list, if present there.
+    pub vector_rank: Option<usize>,
+    /// 1-based rank in the BM25 lexical list, if present there.
+    pub keyword_rank: Option<usize>,
+    pub fused_score: f32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GlobalSearchTrace {
     pub communities_searched: usize,
     pub communities_used: usize,
+    /// Communities that scored below `min_score_vector`/`min_score_text`
+    /// and were dropped before `top_k` was applied.
+    pub communities_dropped_by_threshold: usize,
+}
+
+/// How `GlobalSearchEngine::search_with_mode` should rank community
+/// summaries against the query.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SearchMode {
+    /// Cosine similarity between query and summary embeddings only (the
+    /// original behavior).
+    Vector,
+    /// BM25 over tokenized summary text only.
+    Keyword,
+    /// Fuse the vector and keyword rankings with Reciprocal Rank Fusion.
+    Hybrid,
+}
+
+impl Default for SearchMode {
+    fn default() -> Self {
+        SearchMode::Vector
+    }
 }
 
 pub struct GlobalSearchEngine {
-    embedding_client: EmbeddingClient,
+    embedding_client: Box<dyn Embedder>,
     llm: QueryLLM,
+    rrf_k: f32,
+    embedding_cache: PersistentEmbeddingCache,
+    min_score_vector: f32,
+    min_score_text: f32,
+    embedding_concurrency: usize,
 }
 
 impl GlobalSearchEngine {
-    pub fn new(embedding_client: EmbeddingClient, llm: QueryLLM) -> Self {
+    /// `embedding_client` is a `Box<dyn Embedder>` so callers can point the
+    /// engine at a local Ollama model, a remote OpenAI-compatible endpoint,
+    /// or a custom provider without recompiling.
+    pub fn new(embedding_client: Box<dyn Embedder>, llm: QueryLLM) -> Self {
+        let embedding_cache = PersistentEmbeddingCache::load(DEFAULT_EMBEDDING_CACHE_PATH)
+            .unwrap_or_else(|e| {
+                eprintln!(
+                    "Warning: failed to load embedding cache, starting empty: {}",
+                    e
+                );
+                PersistentEmbeddingCache::empty(DEFAULT_EMBEDDING_CACHE_PATH)
+            });
+
         Self {
             embedding_client,
             llm,
+            rrf_k: DEFAULT_RRF_K,
+            embedding_cache,
+            min_score_vector: DEFAULT_MIN_SCORE_VECTOR,
+            min_score_text: DEFAULT_MIN_SCORE_TEXT,
+            embedding_concurrency: DEFAULT_EMBEDDING_CONCURRENCY,
         }
     }
 
+    /// Override how many summary embedding requests are kept in flight at
+    /// once (defaults to `DEFAULT_EMBEDDING_CONCURRENCY`).
+    pub fn with_embedding_concurrency(mut self, concurrency: usize) -> Self {
+        self.embedding_concurrency = concurrency;
+        self
+    }
+
+    /// Override the Reciprocal Rank Fusion constant `k` (defaults to
+    /// `DEFAULT_RRF_K`). Smaller `k` weights top-ranked hits more heavily.
+    pub fn with_rrf_k(mut self, k: f32) -> Self {
+        self.rrf_k = k;
+        self
+    }
+
+    /// Drop communities whose cosine similarity falls below `min_score`
+    /// before `top_k` is applied (Vector and Hybrid modes).
+    pub fn with_min_score_vector(mut self, min_score: f32) -> Self {
+        self.min_score_vector = min_score;
+        self
+    }
+
+    /// Drop communities whose BM25 score falls below `min_score` before
+    /// `top_k` is applied (Keyword and Hybrid modes).
+    pub fn with_min_score_text(mut self, min_score: f32) -> Self {
+        self.min_score_text = min_score;
+        self
+    }
+
     pub async fn search(&self, query: &str, top_k: usize) -> Result<GlobalSearchResult> {
+        self.search_with_mode(query, top_k, SearchMode::Vector).await
+    }
+
+    pub async fn search_with_mode(
+        &self,
+        query: &str,
+        top_k: usize,
+        mode: SearchMode,
+    ) -> Result<GlobalSearchResult> {
         // Step 1: Load all community summaries
         let summaries = self.load_community_summaries().await?;
         let total_communities = summaries.len();
 
-        // Step 2: Embed the query
-        let query_embedding = self.embedding_client.embed(query).await?;
+        // Step 2: Rank by the requested signal(s), then drop anything below
+        // the configured relevance floor before `top_k` is applied.
+        let (top_communities, communities_dropped_by_threshold) = match mode {
+            SearchMode::Vector => {
+                let ranked = self.rank_by_vector(query, &summaries).await?;
+                let kept = Self::filter_by_threshold(ranked, self.min_score_vector);
+                let dropped = total_communities - kept.len();
+                (Self::references_from_single_list(kept, top_k, true), dropped)
+            }
+            SearchMode::Keyword => {
+                let ranked = self.rank_by_keyword(query, &summaries);
+                let kept = Self::filter_by_threshold(ranked, self.min_score_text);
+                let dropped = total_communities - kept.len();
+                (Self::references_from_single_list(kept, top_k, false), dropped)
+            }
+            SearchMode::Hybrid => {
+                let vector_ranked = self.rank_by_vector(query, &summaries).await?;
+                let keyword_ranked = self.rank_by_keyword(query, &summaries);
 
-        // Step 3: Score summaries by similarity
-        let mut scored_summaries = Vec::new();
-        
-        for summary in &summaries {
-            let summary_embedding = self.embedding_client.embed(&summary.summary).await?;
-            let similarity = Self::cosine_similarity(&query_embedding, &summary_embedding);
-            
-            scored_summaries.push((summary.clone(), similarity));
-        }
+                let vector_kept = Self::filter_by_threshold(vector_ranked, self.min_score_vector);
+                let keyword_kept = Self::filter_by_threshold(keyword_ranked, self.min_score_text);
 
-        // Sort by relevance
-        scored_summaries.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
+                let surviving_ids: std::collections::HashSet<(usize, usize)> = vector_kept
+                    .iter()
+                    .chain(keyword_kept.iter())
+                    .map(|(summary, _)| (summary.level, summary.community_id))
+                    .collect();
+                let dropped = total_communities - surviving_ids.len();
 
-        // Step 4: Take top-k communities
-        let top_communities: Vec<_> = scored_summaries.into_iter()
-            .take(top_k)
-            .collect();
+                (self.fuse_rrf(&vector_kept, &keyword_kept, top_k), dropped)
+            }
+        };
 
         let communities_used = top_communities.len();
 
-        // Step 5: Build global context
+        // Step 3: Build global context
         let context = self.build_global_context(&top_communities);
 
-        // Step 6: Generate synthesis
+        // Step 4: Generate synthesis
         let answer = self.generate_synthesis(query, &context).await?;
 
-        // Build response
-        let community_refs: Vec<CommunityReference> = top_communities.iter()
-            .map(|(summary, score)| CommunityReference {
-                community_id: summary.community_id,
-                summary: summary.summary.clone(),
-                relevance_score: *score,
-            })
-            .collect();
-
         Ok(GlobalSearchResult {
             answer,
-            communities: community_refs,
+            communities: top_communities,
             trace: GlobalSearchTrace {
                 communities_searched: total_communities,
                 communities_used,
+                communities_dropped_by_threshold,
             },
         })
     }
 
+    /// Drop scored communities below `min_score`.
+    fn filter_by_threshold(
+        ranked: Vec<(communities::CommunitySummary, f32)>,
+        min_score: f32,
+    ) -> Vec<(communities::CommunitySummary, f32)> {
+        ranked.into_iter().filter(|(_, score)| *score >= min_score).collect()
+    }
+
+    /// Rank summaries by cosine similarity between the query and each
+    /// summary's embedding, highest first. Summary embeddings are served
+    /// from the content-addressed `embedding_cache` where possible, so a
+    /// given summary is only ever embedded once across all future queries.
+    async fn rank_by_vector(
+        &self,
+        query: &str,
+        summaries: &[communities::CommunitySummary],
+    ) -> Result<Vec<(communities::CommunitySummary, f32)>> {
+        let query_embedding = self.embedding_client.embed(query).await?;
+        let summary_embeddings = self.embeddings_for_summaries(summaries).await?;
+
+        // Cosine similarity is pure CPU work, so score all summaries in
+        // parallel rather than one at a time.
+        let mut scored: Vec<(communities::CommunitySummary, f32)> = summaries
+            .par_iter()
+            .map(|summary| {
+                let digest =
+                    PersistentEmbeddingCache::digest_for(&self.embedding_client.model_id(), &summary.summary);
+                let summary_embedding = &summary_embeddings[&digest];
+                let similarity = Self::cosine_similarity(&query_embedding, summary_embedding);
+                (summary.clone(), similarity)
+            })
+            .collect();
+
+        scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
+        Ok(scored)
+    }
+
+    /// Resolve an embedding for every summary, reusing cached vectors keyed
+    /// by content digest and only issuing network requests for the digests
+    /// that miss. Misses are embedded with up to `embedding_concurrency`
+    /// requests in flight at once, instead of awaiting them one at a time.
+    async fn embeddings_for_summaries(
+        &self,
+        summaries: &[communities::CommunitySummary],
+    ) -> Result<HashMap<String, Vec<f32>>> {
+        let model_id = self.embedding_client.model_id();
+        let digests: Vec<String> = summaries
+            .iter()
+            .map(|s| PersistentEmbeddingCache::digest_for(&model_id, &s.summary))
+            .collect();
+
+        let unique_digests: Vec<String> = {
+            let mut seen = std::collections::HashSet::new();
+            digests.iter().filter(|d| seen.insert((*d).clone())).cloned().collect()
+        };
+
+        let (mut vectors, misses) = self.embedding_cache.embeddings_for_digests(&unique_digests);
+
+        if !misses.is_empty() {
+            let miss_pairs: Vec<(String, String)> = misses
+                .iter()
+                .map(|digest| {
+                    let idx = digests
+                        .iter()
+                        .position(|d| d == digest)
+                        .expect("digest was derived from summaries");
+                    (digest.clone(), summaries[idx].summary.clone())
+                })
+                .collect();
+
+            let embedded: Vec<(String, Vec<f32>)> = stream::iter(miss_pairs)
+                .map(|(digest, text)| async move {
+                    let embedding = self.embedding_client.embed(&text).await?;
+                    Ok::<_, anyhow::Error>((digest, embedding))
+                })
+                .buffer_unordered(self.embedding_concurrency)
+                .collect::<Vec<_>>()
+                .await
+                .into_iter()
+                .collect::<Result<Vec<_>>>()?;
+
+            for (digest, embedding) in embedded {
+                self.embedding_cache.insert(digest.clone(), embedding.clone())?;
+                vectors.insert(digest, embedding);
+            }
+        }
+
+        Ok(vectors)
+    }
+
+    /// Rank summaries by Okapi BM25 over their tokenized summary text,
+    /// highest first.
+    fn rank_by_keyword(
+        &self,
+        query: &str,
+        summaries: &[communities::CommunitySummary],
+    ) -> Vec<(communities::CommunitySummary, f32)> {
+        if summaries.is_empty() {
+            return Vec::new();
+        }
+
+        let query_terms = tokenize(query);
+        let scores = bm25_scores(&query_terms, summaries);
+
+        let mut scored: Vec<(communities::CommunitySummary, f32)> =
+            summaries.iter().cloned().zip(scores).collect();
+        scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
+        scored
+    }
+
+    /// Build `CommunityReference`s from a single ranked list, recording
+    /// each community's rank under the signal that produced the list.
+    fn references_from_single_list(
+        ranked: Vec<(communities::CommunitySummary, f32)>,
+        top_k: usize,
+        is_vector: bool,
+    ) -> Vec<CommunityReference> {
+        ranked
+            .into_iter()
+            .take(top_k)
+            .enumerate()
+            .map(|(i, (summary, score))| {
+                let rank = Some(i + 1);
+                Self::build_reference(
+                    summary,
+                    score,
+                    ScoreBreakdown {
+                        vector_rank: if is_vector { rank } else { None },
+                        keyword_rank: if is_vector { None } else { rank },
+                        fused_score: score,
+                    },
+                )
+            })
+            .collect()
+    }
+
+    /// Fuse the vector and keyword rankings with Reciprocal Rank Fusion:
+    /// `RRF(c) = sum_r 1/(k + rank_r(c))`. A community absent from a list
+    /// contributes 0 for that list.
+    fn fuse_rrf(
+        &self,
+        vector_ranked: &[(communities::CommunitySummary, f32)],
+        keyword_ranked: &[(communities::CommunitySummary, f32)],
+        top_k: usize,
+    ) -> Vec<CommunityReference> {
+        // `community_id` alone is only unique within one Louvain hierarchy
+        // level - every level restarts numbering near 0 - so a level-0 and
+        // a level-2 community can collide. Key on `(level, community_id)`
+        // instead.
+        let mut vector_rank: HashMap<(usize, usize), usize> = HashMap::new();
+        let mut keyword_rank: HashMap<(usize, usize), usize> = HashMap::new();
+        let mut by_id: HashMap<(usize, usize), &communities::CommunitySummary> = HashMap::new();
+
+        for (rank, (summary, _)) in vector_ranked.iter().enumerate() {
+            let key = (summary.level, summary.community_id);
+            vector_rank.insert(key, rank + 1);
+            by_id.entry(key).or_insert(summary);
+        }
+        for (rank, (summary, _)) in keyword_ranked.iter().enumerate() {
+            let key = (summary.level, summary.community_id);
+            keyword_rank.insert(key, rank + 1);
+            by_id.entry(key).or_insert(summary);
+        }
+
+        let mut fused: Vec<((usize, usize), ScoreBreakdown)> = by_id
+            .keys()
+            .map(|&key| {
+                let v_rank = vector_rank.get(&key).copied();
+                let k_rank = keyword_rank.get(&key).copied();
+                let fused_score = v_rank.map(|r| 1.0 / (self.rrf_k + r as f32)).unwrap_or(0.0)
+                    + k_rank.map(|r| 1.0 / (self.rrf_k + r as f32)).unwrap_or(0.0);
+
+                (
+                    key,
+                    ScoreBreakdown {
+                        vector_rank: v_rank,
+                        keyword_rank: k_rank,
+                        fused_score,
+                    },
+                )
+            })
+            .collect();
+
+        fused.sort_by(|a, b| b.1.fused_score.total_cmp(&a.1.fused_score));
+
+        fused
+            .into_iter()
+            .take(top_k)
+            .map(|(key, breakdown)| {
+                let summary = by_id[&key].clone();
+                Self::build_reference(summary, breakdown.fused_score, breakdown)
+            })
+            .collect()
+    }
+
+    fn build_reference(
+        summary: communities::CommunitySummary,
+        relevance_score: f32,
+        score_breakdown: ScoreBreakdown,
+    ) -> CommunityReference {
+        CommunityReference {
+            community_id: summary.community_id,
+            level: summary.level,
+            summary: summary.summary,
+            relevance_score,
+            score_breakdown,
+        }
+    }
+
     async fn load_community_summaries(&self) -> Result<Vec<communities::CommunitySummary>> {
         let dir = PathBuf::from("data/communities");
         let mut summaries = Vec::new();
@@ -115,7 +449,7 @@ impl GlobalSearchEngine {
         let dot: f32 = a.iter().zip(b.iter()).map(|(x, y)| x * y).sum();
         let mag_a: f32 = a.iter().map(|x| x * x).sum::<f32>().sqrt();
         let mag_b: f32 = b.iter().map(|x| x * x).sum::<f32>().sqrt();
-        
+
         if mag_a == 0.0 || mag_b == 0.0 {
             0.0
         } else {
@@ -123,18 +457,21 @@ impl GlobalSearchEngine {
         }
     }
 
-    fn build_global_context(&self, scored_summaries: &[(communities::CommunitySummary, f32)]) -> String {
+    fn build_global_context(&self, references: &[CommunityReference]) -> String {
+        if references.is_empty() {
+            return "No relevant communities were found above the configured relevance threshold.".to_string();
+        }
+
         let mut context = String::new();
 
         context.push_str("THEMATIC COMMUNITIES:\n\n");
 
-        for (i, (summary, score)) in scored_summaries.iter().enumerate() {
+        for (i, reference) in references.iter().enumerate() {
             context.push_str(&format!(
-                "Community {} (relevance: {:.2}):\n{}\n\nKey entities: {}\n\n",
+                "Community {} (relevance: {:.2}):\n{}\n\n",
                 i + 1,
-                score,
-                summary.summary,
-                summary.key_entities.join(", ")
+                reference.relevance_score,
+                reference.summary,
             ));
         }
 
@@ -163,4 +500,49 @@ SYNTHESIS:"#,
 
         self.llm.generate(&prompt).await
     }
-}
\ No newline at end of file
+}
+
+fn tokenize(text: &str) -> Vec<String> {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .map(|t| t.to_string())
+        .collect()
+}
+
+/// Score every community summary in `summaries` against `query_terms` with
+/// Okapi BM25.
+fn bm25_scores(query_terms: &[String], summaries: &[communities::CommunitySummary]) -> Vec<f32> {
+    let docs: Vec<Vec<String>> = summaries.iter().map(|s| tokenize(&s.summary)).collect();
+    let n = docs.len() as f32;
+    let avgdl = docs.iter().map(|d| d.len()).sum::<usize>() as f32 / n;
+
+    let mut doc_freq: HashMap<&str, usize> = HashMap::new();
+    for doc in &docs {
+        for term in query_terms {
+            if doc.iter().any(|t| t == term) {
+                *doc_freq.entry(term.as_str()).or_insert(0) += 1;
+            }
+        }
+    }
+
+    docs.iter()
+        .map(|doc| {
+            let dl = doc.len() as f32;
+            let mut term_freq: HashMap<&str, usize> = HashMap::new();
+            for t in doc {
+                *term_freq.entry(t.as_str()).or_insert(0) += 1;
+            }
+
+            query_terms
+                .iter()
+                .map(|term| {
+                    let n_t = *doc_freq.get(term.as_str()).unwrap_or(&0) as f32;
+                    let idf = ((n - n_t + 0.5) / (n_t + 0.5) + 1.0).ln();
+                    let f = *term_freq.get(term.as_str()).unwrap_or(&0) as f32;
+                    idf * (f * (BM25_K1 + 1.0)) / (f + BM25_K1 * (1.0 - BM25_B + BM25_B * dl / avgdl))
+                })
+                .sum()
+        })
+        .collect()
+}
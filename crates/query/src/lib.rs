@@ -1,7 +1,11 @@
+pub mod embedding_cache;
+pub mod fuzzy_query;
 pub mod llm;
 pub mod local_search;
 pub mod global_search;
 
+pub use embedding_cache::PersistentEmbeddingCache;
+pub use fuzzy_query::{FuzzyMatch, Operation};
 pub use llm::QueryLLM;
 pub use local_search::{LocalSearchEngine, LocalSearchResult};
 pub use global_search::{GlobalSearchEngine, GlobalSearchResult};
@@ -1,11 +1,23 @@
 use anyhow::{Context, Result};
+use async_stream::stream;
+use futures::Stream;
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use common::retry::{self, RetryConfig};
+use common::ContentCache;
+
+/// Default number of (model, prompt) -> response pairs kept in
+/// `QueryLLM`'s in-memory cache.
+const DEFAULT_RESPONSE_CACHE_CAPACITY: usize = 10_000;
 
 #[derive(Clone)]
 pub struct QueryLLM {
     base_url: String,
     model: String,
     client: reqwest::Client,
+    cache: Arc<ContentCache<String>>,
+    retry_cfg: RetryConfig,
 }
 
 #[derive(Serialize)]
@@ -20,12 +32,22 @@ struct OllamaResponse {
     response: String,
 }
 
+/// A single line of Ollama's newline-delimited `stream: true` response.
+#[derive(Deserialize)]
+struct OllamaStreamChunk {
+    response: String,
+    #[serde(default)]
+    done: bool,
+}
+
 impl QueryLLM {
     pub fn new(base_url: String, model: String) -> Self {
         Self {
             base_url,
             model,
             client: reqwest::Client::new(),
+            cache: Arc::new(ContentCache::new(DEFAULT_RESPONSE_CACHE_CAPACITY)),
+            retry_cfg: RetryConfig::default(),
         }
     }
 
@@ -36,31 +58,112 @@ impl QueryLLM {
         )
     }
 
+    /// Override the response cache's capacity (defaults to
+    /// `DEFAULT_RESPONSE_CACHE_CAPACITY`).
+    pub fn with_cache_capacity(mut self, capacity: usize) -> Self {
+        self.cache = Arc::new(ContentCache::new(capacity));
+        self
+    }
+
+    /// Override the retry/backoff settings used by `generate` (defaults to
+    /// `RetryConfig::default()`), so a caller can thread its own layered
+    /// retry config through instead of the hardcoded default.
+    pub fn with_retry_config(mut self, retry_cfg: RetryConfig) -> Self {
+        self.retry_cfg = retry_cfg;
+        self
+    }
+
+    /// Generate a complete response, serving from the content-addressed
+    /// cache when this exact `(model, prompt)` pair has been seen before.
     pub async fn generate(&self, prompt: &str) -> Result<String> {
+        if let Some(cached) = self.cache.get(&self.model, prompt) {
+            return Ok(cached);
+        }
+
         let url = format!("{}/api/generate", self.base_url);
 
+        let ollama_response: OllamaResponse = retry::retry_with_backoff(
+            &self.retry_cfg,
+            || async {
+                let request = OllamaRequest {
+                    model: self.model.clone(),
+                    prompt: prompt.to_string(),
+                    stream: false,
+                };
+
+                let response = self.client
+                    .post(&url)
+                    .json(&request)
+                    .send()
+                    .await
+                    .context("Failed to send request to Ollama")?;
+
+                if !response.status().is_success() {
+                    return Err(anyhow::Error::new(retry::HttpStatusError(response.status()))
+                        .context(format!("Ollama request failed: {}", response.status())));
+                }
+
+                response
+                    .json()
+                    .await
+                    .context("Failed to parse Ollama response")
+            },
+            retry::is_transient_error,
+        )
+        .await?;
+
+        self.cache.put(&self.model, prompt, ollama_response.response.clone());
+        Ok(ollama_response.response)
+    }
+
+    /// Stream the answer token-by-token by consuming Ollama's
+    /// newline-delimited `stream: true` response.
+    pub fn generate_stream(&self, prompt: &str) -> impl Stream<Item = Result<String>> {
+        let client = self.client.clone();
+        let url = format!("{}/api/generate", self.base_url);
         let request = OllamaRequest {
             model: self.model.clone(),
             prompt: prompt.to_string(),
-            stream: false,
+            stream: true,
         };
 
-        let response = self.client
-            .post(&url)
-            .json(&request)
-            .send()
-            .await
-            .context("Failed to send request to Ollama")?;
+        stream! {
+            let response = client
+                .post(&url)
+                .json(&request)
+                .send()
+                .await
+                .context("Failed to send streaming request to Ollama")?;
 
-        if !response.status().is_success() {
-            anyhow::bail!("Ollama request failed: {}", response.status());
-        }
+            if !response.status().is_success() {
+                anyhow::bail!("Ollama streaming request failed: {}", response.status());
+            }
 
-        let ollama_response: OllamaResponse = response
-            .json()
-            .await
-            .context("Failed to parse Ollama response")?;
+            let mut response = response;
+            let mut buffer = String::new();
 
-        Ok(ollama_response.response)
+            while let Some(bytes) = response.chunk().await.context("Failed to read stream chunk")? {
+                buffer.push_str(&String::from_utf8_lossy(&bytes));
+
+                while let Some(newline_pos) = buffer.find('\n') {
+                    let line = buffer[..newline_pos].trim().to_string();
+                    buffer.drain(..=newline_pos);
+
+                    if line.is_empty() {
+                        continue;
+                    }
+
+                    let chunk: OllamaStreamChunk = serde_json::from_str(&line)
+                        .context("Failed to parse Ollama stream chunk")?;
+
+                    if !chunk.response.is_empty() {
+                        yield Ok(chunk.response);
+                    }
+                    if chunk.done {
+                        return;
+                    }
+                }
+            }
+        }
     }
 }
\ No newline at end of file
@@ -3,8 +3,9 @@ use neo4rs::{Graph, Query};
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 
+use crate::fuzzy_query::{self, FuzzyMatch};
 use crate::llm::QueryLLM;
-use index::EmbeddingClient;
+use index::{Embedder, EmbeddingClient};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LocalSearchResult {
@@ -26,6 +27,11 @@ pub struct SearchTrace {
     pub entities_found: usize,
     pub entities_expanded: usize,
     pub context_size: usize,
+    /// Entities pulled in as extra graph-expansion seeds because their name
+    /// fuzzy-matched the query's terms (a misspelled or partial entity name
+    /// that the vector search alone would have missed), alongside which
+    /// term matched and at what edit distance.
+    pub fuzzy_matches: Vec<FuzzyMatch>,
 }
 
 pub struct LocalSearchEngine {
@@ -54,12 +60,27 @@ impl LocalSearchEngine {
     }
 
     pub async fn search(&self, query: &str, top_k: usize) -> Result<LocalSearchResult> {
+        self.search_with_options(query, top_k, false).await
+    }
+
+    /// Like `search`, but when `rerank_in_process` is set, Qdrant is asked
+    /// to return each candidate's stored vector alongside its payload, and
+    /// the score is recomputed in-process as a dot product against the
+    /// (unit-normalized) query embedding instead of trusting Qdrant's own
+    /// score — scoring stays comparable across embedding providers without
+    /// a second network round-trip.
+    pub async fn search_with_options(
+        &self,
+        query: &str,
+        top_k: usize,
+        rerank_in_process: bool,
+    ) -> Result<LocalSearchResult> {
         // Step 1: Embed the query
         let query_embedding = self.embedding_client.embed(query).await
             .context("Failed to embed query")?;
 
         // Step 2: Vector search via REST API
-        let points = self.search_qdrant_rest(query_embedding, top_k).await
+        let points = self.search_qdrant_rest(query_embedding, top_k, rerank_in_process).await
             .context("Failed to search Qdrant")?;
 
         let chunks_retrieved = points.len();
@@ -88,6 +109,14 @@ impl LocalSearchEngine {
 
         let entities_found = entity_ids.len();
 
+        // Step 3b: Fuzzy-match the query's terms against entity names so a
+        // typo'd or partial entity name still seeds graph expansion, even
+        // when it didn't surface through the vector search above.
+        let fuzzy_matches = self.fuzzy_match_entities(query).await?;
+        for fuzzy_match in &fuzzy_matches {
+            entity_ids.insert(fuzzy_match.entity_id.clone());
+        }
+
         // Step 4: Expand graph (1-2 hops)
         let expanded_entities = if !entity_ids.is_empty() {
             self.expand_graph(&entity_ids, 2).await?
@@ -123,20 +152,27 @@ impl LocalSearchEngine {
                 entities_found,
                 entities_expanded,
                 context_size: context.len(),
+                fuzzy_matches,
             },
         })
     }
 
-    async fn search_qdrant_rest(&self, query_embedding: Vec<f32>, top_k: usize) -> Result<Vec<QdrantPoint>> {
+    async fn search_qdrant_rest(
+        &self,
+        query_embedding: Vec<f32>,
+        top_k: usize,
+        rerank_in_process: bool,
+    ) -> Result<Vec<QdrantPoint>> {
         use serde_json::json;
-        
+
         let client = reqwest::Client::new();
         let url = format!("{}/collections/{}/points/search", self.qdrant_url, self.collection_name);
-        
+
         let body = json!({
-            "vector": query_embedding,
+            "vector": { "name": "dense", "vector": &query_embedding },
             "limit": top_k,
-            "with_payload": true
+            "with_payload": true,
+            "with_vector": rerank_in_process,
         });
 
         let response = client.post(&url)
@@ -159,7 +195,19 @@ impl LocalSearchEngine {
 
         let mut parsed_points = Vec::new();
         for point in points {
-            let score = point["score"].as_f64().unwrap_or(0.0) as f32;
+            let mut score = point["score"].as_f64().unwrap_or(0.0) as f32;
+
+            if rerank_in_process {
+                if let Some(stored) = point["vector"]["dense"].as_array() {
+                    let stored: Vec<f32> = stored
+                        .iter()
+                        .filter_map(|v| v.as_f64())
+                        .map(|v| v as f32)
+                        .collect();
+                    score = dot(&query_embedding, &stored);
+                }
+            }
+
             let payload = point["payload"].as_object()
                 .context("Missing payload")?;
 
@@ -167,12 +215,12 @@ impl LocalSearchEngine {
                 .and_then(|v| v.as_str())
                 .unwrap_or("")
                 .to_string();
-            
+
             let text = payload.get("text")
                 .and_then(|v| v.as_str())
                 .unwrap_or("")
                 .to_string();
-            
+
             let entity_ids = payload.get("entity_ids")
                 .and_then(|v| v.as_str())
                 .unwrap_or("")
@@ -189,6 +237,40 @@ impl LocalSearchEngine {
         Ok(parsed_points)
     }
 
+    /// Evaluate the query's fuzzy boolean tree (see `fuzzy_query`) against
+    /// every entity name in the graph, returning one `FuzzyMatch` per
+    /// matched query term of each entity whose name satisfies it.
+    async fn fuzzy_match_entities(&self, query: &str) -> Result<Vec<FuzzyMatch>> {
+        let tree = fuzzy_query::build_query_tree(query);
+
+        let mut result = self.neo4j
+            .execute(Query::new("MATCH (e:Entity) RETURN e.id as id, e.name as name".to_string()))
+            .await?;
+
+        let mut matches = Vec::new();
+        while let Some(row) = result.next().await? {
+            let id: String = row.get("id")?;
+            let name: String = row.get("name")?;
+            let name_words: Vec<String> = name
+                .split_whitespace()
+                .map(|w| w.to_lowercase())
+                .collect();
+
+            if let Some(leaves) = fuzzy_query::evaluate(&tree, &name_words) {
+                for (term, _word, distance) in leaves {
+                    matches.push(FuzzyMatch {
+                        entity_id: id.clone(),
+                        entity_name: name.clone(),
+                        term,
+                        distance,
+                    });
+                }
+            }
+        }
+
+        Ok(matches)
+    }
+
     async fn expand_graph(&self, seed_entities: &HashSet<String>, hops: usize) -> Result<HashSet<String>> {
         let mut expanded = seed_entities.clone();
 
@@ -338,6 +420,12 @@ ANSWER:"#,
     }
 }
 
+/// Dot product of two equal-length vectors. Since `Embedder` implementations
+/// L2-normalize their output, this is equivalent to cosine similarity.
+fn dot(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
 #[derive(Debug, Clone)]
 struct QdrantPoint {
     score: f32,